@@ -50,6 +50,13 @@ echo -e "${BOLD}── 5. Register as Validator ──────────
 rpc "pecu_registerValidator" "[\"$ALICE_ADDR\",\"1000000000000000000\"]"
 rpc "pecu_getValidators"
 
+# Adjusting stake requires a GAK session for the validator's wallet
+GAK_KEY=$(curl -s "$RPC" \
+  -X POST -H "Content-Type: application/json" \
+  -d "{\"jsonrpc\":\"2.0\",\"method\":\"gak_connect\",\"params\":[\"$ALICE_ADDR\",\"validator-cli\",3600],\"id\":1}" \
+  | python3 -c "import sys,json; print(json.load(sys.stdin)['result']['keyId'])" 2>/dev/null)
+rpc "pecu_setValidatorStake" "[\"$ALICE_ADDR\",\"2000000000000000000\",\"$GAK_KEY\"]"
+
 echo -e "${BOLD}── 6. Tokenomics ────────────────────────────────────────${NC}"
 rpc "pecu_getTokenomics"
 rpc "pecu_getHalvingSchedule"
@@ -21,8 +21,10 @@ use crate::crypto;
 use chrono::Utc;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{Read, Write};
 use std::sync::Arc;
+use tokio::sync::watch;
 
 // ─── Transaction Types ────────────────────────────────────────────────────────
 
@@ -91,6 +93,10 @@ pub struct Transaction {
 
     pub block_height: Option<u64>,
     pub nonce: u64,
+
+    /// Hash of a recent block; the tx expires once it leaves the window
+    #[serde(default)]
+    pub recent_blockhash: Option<String>,
 }
 
 impl Transaction {
@@ -121,17 +127,9 @@ impl Transaction {
             note.as_deref().unwrap_or(""),
             is_escrow
         );
-        let tx_hash = crypto::compute_block_address(
-            sender,
-            receiver,
-            &amount.to_string(),
-            timestamp,
-            note.as_deref().unwrap_or(""),
-            is_escrow,
-        );
 
-        Transaction {
-            tx_hash,
+        let mut tx = Transaction {
+            tx_hash: String::new(),
             tx_type,
             sender: sender.to_string(),
             receiver: receiver.to_string(),
@@ -150,15 +148,69 @@ impl Transaction {
             signature: String::new(),
             block_height: None,
             nonce,
-        }
+            recent_blockhash: None,
+        };
+        tx.tx_hash = tx.compute_hash();
+        tx
+    }
+
+    /// Hash over everything a block commits to: the whitepaper block address
+    /// (sender, receiver, amount, timestamp, note, escrow flag) bound to the
+    /// type, fee, nonce and optional token/escrow/blockhash fields. Signature
+    /// and confirmation flags are not covered.
+    pub fn compute_hash(&self) -> String {
+        let block_address = crypto::compute_block_address(
+            &self.sender,
+            &self.receiver,
+            &self.amount.to_string(),
+            self.timestamp,
+            self.note.as_deref().unwrap_or(""),
+            self.is_escrow,
+        );
+        let bound = serde_json::to_vec(&(
+            block_address,
+            &self.tx_type,
+            self.gas_fee.to_string(),
+            self.nonce,
+            self.escrow_release_date,
+            &self.contract_address,
+            &self.token_id,
+            &self.call_data,
+            &self.recent_blockhash,
+        ))
+        .unwrap_or_default();
+        crypto::sha512(&bound)
+    }
+
+    /// Bind the transaction to a recent block so it cannot be replayed
+    /// once that block falls out of the validity window
+    pub fn with_recent_blockhash(mut self, blockhash: &str) -> Self {
+        self.recent_blockhash = Some(blockhash.to_string());
+        self.tx_hash = self.compute_hash();
+        self
+    }
+
+    /// Size of the transaction as it is carried in a block
+    pub fn encoded_len(&self) -> usize {
+        serde_json::to_vec(self)
+            .map(|v| v.len())
+            .unwrap_or(usize::MAX)
     }
 
     pub fn sign(&mut self, private_key: &str) {
         let data = format!(
-            "{}{}{}{}{}",
-            self.tx_hash, self.sender, self.receiver, self.amount, self.timestamp
+            "{}{}{}{}{}{}",
+            self.tx_hash,
+            self.sender,
+            self.receiver,
+            self.amount,
+            self.timestamp,
+            self.recent_blockhash.as_deref().unwrap_or("")
+        );
+        let combined = format!(
+            "{private_key}{}",
+            crypto::domain_separated(crypto::DOMAIN_TRANSACTION, &data)
         );
-        let combined = format!("{private_key}{data}");
         self.signature = crypto::sha512(combined.as_bytes());
     }
 
@@ -193,18 +245,12 @@ pub struct BlockHeader {
 }
 
 impl BlockHeader {
+    /// SHA-256 over the canonical (JSON, fixed field order) encoding of the
+    /// whole header, so the hash commits to every field and adjacent fields
+    /// cannot run into each other
     pub fn compute_hash(&self) -> String {
-        let data = format!(
-            "{}{}{}{}{}{}{}",
-            self.height,
-            self.previous_hash,
-            self.merkle_root,
-            self.timestamp,
-            self.validator,
-            self.pot_proof.output,
-            self.state_hash,
-        );
-        crypto::sha256(data.as_bytes())
+        let canonical = serde_json::to_vec(self).expect("header serialization failed");
+        crypto::sha256(&canonical)
     }
 }
 
@@ -227,6 +273,25 @@ impl Block {
         pot_proof: VdfProof,
     ) -> Self {
         let timestamp = Utc::now().timestamp();
+        Self::new_at(
+            height,
+            previous_hash,
+            transactions,
+            validator,
+            pot_proof,
+            timestamp,
+        )
+    }
+
+    /// Build a block with an explicit header timestamp
+    pub fn new_at(
+        height: u64,
+        previous_hash: &str,
+        transactions: Vec<Transaction>,
+        validator: &str,
+        pot_proof: VdfProof,
+        timestamp: i64,
+    ) -> Self {
         let tx_hashes: Vec<String> = transactions.iter().map(|t| t.tx_hash.clone()).collect();
         let merkle_root = crypto::compute_merkle_root(&tx_hashes);
 
@@ -284,14 +349,17 @@ impl Block {
             signature: "genesis".to_string(),
             block_height: Some(0),
             nonce: 0,
+            recent_blockhash: None,
         };
 
-        Block::new(
+        // Fixed timestamp so every node derives the same genesis hash
+        Block::new_at(
             0,
             "0000000000000000000000000000000000000000000000000000000000000000",
             vec![genesis_tx],
             "PecuNovusFoundation",
             genesis_proof,
+            1484438400,
         )
     }
 
@@ -302,10 +370,50 @@ impl Block {
     pub fn total_burned(&self) -> u128 {
         self.transactions.iter().map(|t| t.burned_amount()).sum()
     }
+
+    /// True if the transactions are the ones the header commits to: every
+    /// tx_hash re-derives from its transaction, and the merkle root and count
+    /// match. Genesis must equal `Block::genesis()` exactly.
+    pub fn verify_body(&self) -> bool {
+        if self.header.height == 0 {
+            return serde_json::to_vec(self).ok() == serde_json::to_vec(&Block::genesis()).ok();
+        }
+        let tx_hashes: Vec<String> = self
+            .transactions
+            .iter()
+            .map(|t| t.tx_hash.clone())
+            .collect();
+        self.transactions
+            .iter()
+            .all(|t| t.compute_hash() == t.tx_hash)
+            && self.header.tx_count as usize == self.transactions.len()
+            && crypto::compute_merkle_root(&tx_hashes) == self.header.merkle_root
+    }
+
+    /// True if every block's hash matches its header, its transactions match
+    /// the header (`verify_body`), and each block links to the one before it
+    /// by previous_hash and consecutive height
+    pub fn verify_chain(blocks: &[Block]) -> bool {
+        blocks
+            .iter()
+            .all(|b| b.header.compute_hash() == b.hash && b.verify_body())
+            && blocks.windows(2).all(|pair| {
+                pair[1].header.previous_hash == pair[0].hash
+                    && pair[1].header.height == pair[0].header.height + 1
+            })
+    }
 }
 
 // ─── Blockchain ───────────────────────────────────────────────────────────────
 
+/// Leading bytes of a ledger archive written by `Blockchain::export_range`
+pub const LEDGER_ARCHIVE_MAGIC: &[u8; 8] = b"PECULDGR";
+/// Version 2: block hashes cover the canonical header encoding
+pub const LEDGER_ARCHIVE_VERSION: u32 = 2;
+/// Largest encoded block `import_range` will read: the block limit plus
+/// room for the header and JSON framing
+const MAX_ARCHIVED_BLOCK_BYTES: usize = Blockchain::MAX_BLOCK_BYTES + 64 * 1024;
+
 #[derive(Debug)]
 pub struct Blockchain {
     pub chain: Arc<RwLock<Vec<Block>>>,
@@ -319,6 +427,16 @@ pub struct Blockchain {
     pub nonces: Arc<RwLock<HashMap<String, u64>>>,
     /// ERC-20 token allowances: (owner, spender, contract) -> amount
     pub allowances: Arc<RwLock<HashMap<(String, String, String), u128>>>,
+    /// Transactions removed from the mempool without inclusion: hash -> reason
+    pub dropped: Arc<RwLock<DroppedTxs>>,
+    /// Senders (system/governance accounts) whose txs are drained first
+    pub priority_senders: Arc<RwLock<HashSet<String>>>,
+    /// How drain_mempool fills the rest of a block
+    pub drain_policy: Arc<RwLock<DrainPolicy>>,
+    /// Committed transactions: tx hash -> block height
+    pub tx_index: Arc<RwLock<HashMap<String, u64>>>,
+    /// Height of the newest committed block, for long-polling RPC waiters
+    tip: watch::Sender<u64>,
 }
 
 impl Blockchain {
@@ -334,13 +452,34 @@ impl Blockchain {
     /// Gas fee burn: 50% of collected fees
     pub const BURN_RATIO: u128 = 50;
 
+    /// Number of most recent blocks whose hash a transaction may reference
+    pub const RECENT_BLOCKHASH_WINDOW: usize = 150;
+
+    /// Most transactions one sender may have waiting in the mempool
+    pub const MAX_PENDING_PER_SENDER: usize = 64;
+
+    /// Share of each drained batch reserved for priority senders, so they
+    /// cannot crowd out everyone else
+    pub const PRIORITY_RESERVED_PERCENT: usize = 25;
+
+    /// Most transactions a block may carry
+    pub const MAX_BLOCK_TXS: usize = 10_000;
+
+    /// Most bytes of encoded transactions a block may carry
+    pub const MAX_BLOCK_BYTES: usize = 8 * 1024 * 1024;
+
+    /// Most dropped transactions whose reason is remembered
+    pub const MAX_DROPPED_TXS: usize = 10_000;
+
     pub fn new() -> Self {
         let genesis = Block::genesis();
         let mut balances = HashMap::new();
+        let mut tx_index = HashMap::new();
 
         // Initialize genesis balance
         for tx in &genesis.transactions {
             *balances.entry(tx.receiver.clone()).or_insert(0) += tx.amount;
+            tx_index.insert(tx.tx_hash.clone(), 0);
         }
 
         Blockchain {
@@ -350,6 +489,11 @@ impl Blockchain {
             total_burned: Arc::new(RwLock::new(0)),
             nonces: Arc::new(RwLock::new(HashMap::new())),
             allowances: Arc::new(RwLock::new(HashMap::new())),
+            dropped: Arc::new(RwLock::new(DroppedTxs::default())),
+            priority_senders: Arc::new(RwLock::new(HashSet::new())),
+            drain_policy: Arc::new(RwLock::new(DrainPolicy::default())),
+            tx_index: Arc::new(RwLock::new(tx_index)),
+            tip: watch::channel(0).0,
         }
     }
 
@@ -369,17 +513,113 @@ impl Blockchain {
         *self.nonces.read().get(address).unwrap_or(&0)
     }
 
+    /// (balance, nonce) for each address in order, read under one lock so
+    /// the results are mutually consistent; None for unknown accounts
+    pub fn get_accounts(&self, addresses: &[&str]) -> Vec<Option<(u128, u64)>> {
+        let balances = self.balances.read();
+        let nonces = self.nonces.read();
+        addresses
+            .iter()
+            .map(|address| {
+                let balance = balances.get(*address).copied();
+                let nonce = nonces.get(*address).copied();
+                if balance.is_none() && nonce.is_none() {
+                    None
+                } else {
+                    Some((balance.unwrap_or(0), nonce.unwrap_or(0)))
+                }
+            })
+            .collect()
+    }
+
+    /// Check the whole local chain with `Block::verify_chain`
+    pub fn verify_chain(&self) -> bool {
+        Block::verify_chain(&self.chain.read())
+    }
+
+    /// Merkle root over every account's balance and nonce, ordered by address
+    pub fn state_root(&self) -> String {
+        let balances = self.balances.read();
+        let nonces = self.nonces.read();
+        let mut addresses: Vec<&String> = balances.keys().chain(nonces.keys()).collect();
+        addresses.sort();
+        addresses.dedup();
+        let leaves: Vec<String> = addresses
+            .into_iter()
+            .map(|address| {
+                let balance = balances.get(address).copied().unwrap_or(0);
+                let nonce = nonces.get(address).copied().unwrap_or(0);
+                crypto::sha256(format!("{address}:{balance}:{nonce}").as_bytes())
+            })
+            .collect();
+        crypto::compute_merkle_root(&leaves)
+    }
+
+    /// Rebuild state by re-applying `blocks`, which must start at genesis.
+    /// Block application is deterministic, so replaying the same blocks
+    /// always yields the same `state_root`.
+    pub fn replay(blocks: &[Block]) -> Result<Blockchain, String> {
+        let replayed = Blockchain::new();
+        let (genesis, rest) = blocks
+            .split_first()
+            .ok_or_else(|| "No blocks to replay".to_string())?;
+        if genesis.hash != replayed.latest_block().hash {
+            return Err("Replay must start at the genesis block".to_string());
+        }
+        if !Block::verify_chain(blocks) {
+            return Err("Blocks do not form a valid hash chain".to_string());
+        }
+        for block in rest {
+            replayed.commit_block(block.clone())?;
+        }
+        Ok(replayed)
+    }
+
+    /// True if `hash` is one of the last RECENT_BLOCKHASH_WINDOW block hashes
+    pub fn is_recent_blockhash(&self, hash: &str) -> bool {
+        self.chain
+            .read()
+            .iter()
+            .rev()
+            .take(Self::RECENT_BLOCKHASH_WINDOW)
+            .any(|b| b.hash == hash)
+    }
+
     pub fn add_to_mempool(&self, tx: Transaction) -> Result<String, String> {
+        let hash = tx.tx_hash.clone();
+        if self.tx_index.read().contains_key(&hash) {
+            return Err(format!("Transaction {hash} already committed"));
+        }
         // Validate transaction
         self.validate_transaction(&tx)?;
-        let hash = tx.tx_hash.clone();
-        self.mempool.write().push(tx);
+        let mut pool = self.mempool.write();
+        if pool.iter().any(|p| p.tx_hash == hash) {
+            return Err(format!("Transaction {hash} already pending"));
+        }
+        // Validator rewards are issued by the node itself, one per validator
+        if tx.tx_type != TransactionType::ValidatorReward {
+            let pending = pool.iter().filter(|p| p.sender == tx.sender).count();
+            if pending >= Self::MAX_PENDING_PER_SENDER {
+                return Err(format!(
+                    "Too many pending transactions for {}: {pending}",
+                    tx.sender
+                ));
+            }
+        }
+        pool.push(tx);
         Ok(hash)
     }
 
     pub fn validate_transaction(&self, tx: &Transaction) -> Result<(), String> {
-        let balances = self.balances.read();
-        let sender_balance = balances.get(&tx.sender).copied().unwrap_or(0);
+        if tx.encoded_len() > Self::MAX_BLOCK_BYTES {
+            return Err("Transaction too large to fit in a block".to_string());
+        }
+        if tx.compute_hash() != tx.tx_hash {
+            return Err("Transaction hash does not match its contents".to_string());
+        }
+
+        // Not held across is_recent_blockhash: commits lock the chain first
+        let sender_balance = self.get_balance(&tx.sender);
         let total_cost = tx.amount.saturating_add(tx.gas_fee);
 
         if tx.tx_type == TransactionType::Transfer || tx.tx_type == TransactionType::Escrow {
@@ -393,6 +633,14 @@ impl Blockchain {
             }
         }
 
+        // Transactions without a blockhash are still accepted (legacy clients,
+        // system rewards); those that carry one must reference a recent block
+        if let Some(blockhash) = &tx.recent_blockhash {
+            if !self.is_recent_blockhash(blockhash) {
+                return Err(format!("Blockhash expired or unknown: {blockhash}"));
+            }
+        }
+
         // Check nonce
         let expected_nonce = self.get_nonce(&tx.sender);
         if tx.nonce < expected_nonce {
@@ -403,62 +651,326 @@ impl Blockchain {
     }
 
     /// Commit a new block (called by Validator after PoT consensus)
+    ///
+    /// Transactions are applied to a staged copy of the touched accounts; if
+    /// any of them fails, nothing is written back and the block is rejected.
+    /// Used for blocks whose hash is already fixed (replay, archive import).
     pub fn commit_block(&self, block: Block) -> Result<(), String> {
-        // Apply all transactions
+        Self::check_block_limits(&block)?;
+        // The chain stays locked from the linkage check to the push, so two
+        // blocks can never be committed at the same height
+        let mut chain = self.chain.write();
+        Self::check_extends_tip(&chain, &block)?;
+        self.apply_transactions(&block.transactions, &block.header.validator, false)?;
+        self.append_block(&mut chain, block);
+        Ok(())
+    }
+
+    /// Seal and commit the next block from drained mempool transactions.
+    ///
+    /// Each transaction is staged on its own: one that fails (e.g. a second
+    /// transfer that overdraws an account) is left out and marked dropped
+    /// with its reason, and the block is sealed with the rest. If the tip
+    /// moved since `previous_hash` was read, nothing is committed and the
+    /// transactions return to the mempool.
+    pub fn produce_block(
+        &self,
+        height: u64,
+        previous_hash: &str,
+        transactions: Vec<Transaction>,
+        validator: &str,
+        pot_proof: VdfProof,
+    ) -> Result<Block, String> {
+        let block = Block::new(height, previous_hash, transactions, validator, pot_proof);
+        Self::check_block_limits(&block)?;
+        let mut chain = self.chain.write();
+        if let Err(e) = Self::check_extends_tip(&chain, &block) {
+            // Another block won this height; the drained txs go back to the pool
+            drop(chain);
+            self.mempool.write().splice(0..0, block.transactions);
+            return Err(e);
+        }
+        let failed = self.apply_transactions(&block.transactions, validator, true)?;
+        if failed.is_empty() {
+            self.append_block(&mut chain, block.clone());
+            return Ok(block);
+        }
+
+        let total = block.transactions.len();
+        let mut failed = failed.into_iter().peekable();
+        let mut kept = Vec::with_capacity(total);
         {
-            let mut balances = self.balances.write();
-            let mut burned = self.total_burned.write();
-            let mut nonces = self.nonces.write();
-
-            for tx in &block.transactions {
-                match tx.tx_type {
-                    TransactionType::Transfer | TransactionType::Escrow => {
-                        let sender_bal = balances.entry(tx.sender.clone()).or_insert(0);
-                        if tx.sender != "0x0000000000000000000000000000000000000000" {
-                            *sender_bal = sender_bal.saturating_sub(tx.amount + tx.gas_fee);
-                        }
-                        *balances.entry(tx.receiver.clone()).or_insert(0) += tx.amount;
-
-                        // Burn 50% of gas fees
-                        *burned += tx.burned_amount();
-
-                        // Validator gets 50% of gas fee
-                        *balances.entry(block.header.validator.clone()).or_insert(0) +=
-                            tx.gas_fee - tx.burned_amount();
-                    }
-                    TransactionType::ValidatorReward => {
-                        *balances.entry(tx.receiver.clone()).or_insert(0) += tx.amount;
-                    }
-                    TransactionType::Burn => {
-                        let sender_bal = balances.entry(tx.sender.clone()).or_insert(0);
-                        *sender_bal = sender_bal.saturating_sub(tx.amount);
-                        *burned += tx.amount;
-                    }
-                    TransactionType::ERC20Approve => {
-                        // allowance handled by token layer
-                    }
-                    _ => {
-                        // Token and contract txs handled by token/escrow layers
+            let mut dropped = self.dropped.write();
+            for (idx, tx) in block.transactions.into_iter().enumerate() {
+                match failed.next_if(|(i, _)| *i == idx) {
+                    Some((_, reason)) => {
+                        dropped.insert(tx.tx_hash, format!("Failed to apply: {reason}"));
                     }
+                    None => kept.push(tx),
                 }
+            }
+        }
+        if kept.is_empty() {
+            return Err(format!("All {total} transactions in block failed"));
+        }
 
-                // Increment nonce
-                if tx.sender != "0x0000000000000000000000000000000000000000" {
-                    let nonce = nonces.entry(tx.sender.clone()).or_insert(0);
-                    *nonce += 1;
+        let block = Block::new_at(
+            height,
+            previous_hash,
+            kept,
+            validator,
+            block.header.pot_proof,
+            block.header.timestamp,
+        );
+        self.append_block(&mut chain, block.clone());
+        Ok(block)
+    }
+
+    /// Stage `txs` against the committed state and write the result back.
+    /// With `skip_failed` a failing transaction is left out and returned
+    /// with its reason; otherwise the first failure aborts with nothing
+    /// written.
+    fn apply_transactions(
+        &self,
+        txs: &[Transaction],
+        validator: &str,
+        skip_failed: bool,
+    ) -> Result<Vec<(usize, String)>, String> {
+        let mut balances = self.balances.write();
+        let mut burned = self.total_burned.write();
+        let mut nonces = self.nonces.write();
+
+        let mut staged = StagedState::new(&balances, &nonces, *burned);
+        let mut failed = Vec::new();
+        for (idx, tx) in txs.iter().enumerate() {
+            if let Err(e) = staged.apply(tx, validator) {
+                if !skip_failed {
+                    return Err(format!("Transaction {} failed: {e}", tx.tx_hash));
                 }
+                failed.push((idx, e));
             }
         }
 
-        self.chain.write().push(block);
+        let (staged_balances, staged_nonces, staged_burned) = staged.into_parts();
+        balances.extend(staged_balances);
+        nonces.extend(staged_nonces);
+        *burned = staged_burned;
+        Ok(failed)
+    }
+
+    /// Err unless `block` is the next height and links to the current tip
+    fn check_extends_tip(chain: &[Block], block: &Block) -> Result<(), String> {
+        let tip = chain.last().expect("chain always holds genesis");
+        if block.header.height != tip.header.height + 1 || block.header.previous_hash != tip.hash {
+            return Err(format!(
+                "Block {} does not extend local tip {}",
+                block.header.height, tip.header.height
+            ));
+        }
+        Ok(())
+    }
+
+    /// Push onto the locked chain; callers have run `check_extends_tip`
+    /// under the same lock
+    fn append_block(&self, chain: &mut Vec<Block>, block: Block) {
+        // Index after the push so a lookup never points past the tip
+        let height = block.header.height;
+        let hashes: Vec<String> = block
+            .transactions
+            .iter()
+            .map(|t| t.tx_hash.clone())
+            .collect();
+        chain.push(block);
+        // First inclusion wins; a repeated hash never repoints the index
+        let mut index = self.tx_index.write();
+        for hash in hashes {
+            index.entry(hash).or_insert(height);
+        }
+        drop(index);
+        self.tip.send_replace(height);
+    }
+
+    /// Watch the tip height; the receiver wakes whenever a block is appended
+    pub fn subscribe_tip(&self) -> watch::Receiver<u64> {
+        self.tip.subscribe()
+    }
+
+    /// Reject blocks over MAX_BLOCK_TXS transactions or MAX_BLOCK_BYTES
+    pub fn check_block_limits(block: &Block) -> Result<(), String> {
+        if block.transactions.len() > Self::MAX_BLOCK_TXS {
+            return Err(format!(
+                "Block carries {} transactions, limit is {}",
+                block.transactions.len(),
+                Self::MAX_BLOCK_TXS
+            ));
+        }
+        let bytes: usize = block.transactions.iter().map(|t| t.encoded_len()).sum();
+        if bytes > Self::MAX_BLOCK_BYTES {
+            return Err(format!(
+                "Block carries {bytes} bytes of transactions, limit is {}",
+                Self::MAX_BLOCK_BYTES
+            ));
+        }
         Ok(())
     }
 
-    /// Take up to `max_txs` pending transactions from mempool
+    /// Replace the priority allowlist with the comma-separated addresses in
+    /// `list` (as read from PECU_PRIORITY_SENDERS). Returns how many were set.
+    pub fn set_priority_senders(&self, list: &str) -> usize {
+        let senders: HashSet<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        let count = senders.len();
+        *self.priority_senders.write() = senders;
+        count
+    }
+
+    /// Take up to `max_txs` pending transactions from mempool, within the
+    /// block limits
     pub fn drain_mempool(&self, max_txs: usize) -> Vec<Transaction> {
+        self.drain_mempool_within(max_txs, Self::MAX_BLOCK_BYTES)
+    }
+
+    /// Take up to `max_txs` pending transactions totalling at most
+    /// `max_bytes`. Transactions from priority senders go first, up to
+    /// PRIORITY_RESERVED_PERCENT of the batch; the rest is filled according
+    /// to the drain policy. Transactions that do not fit in what is left of
+    /// the budget are skipped and stay at the front of the mempool for the
+    /// next block; any larger than `max_bytes` on its own can never fit and
+    /// is dropped.
+    pub fn drain_mempool_within(&self, max_txs: usize, max_bytes: usize) -> Vec<Transaction> {
+        let max_txs = max_txs.min(Self::MAX_BLOCK_TXS);
+        let max_bytes = max_bytes.min(Self::MAX_BLOCK_BYTES);
+        let priority = self.priority_senders.read();
+        let policy = *self.drain_policy.read();
         let mut pool = self.mempool.write();
-        let drain_count = max_txs.min(pool.len());
-        pool.drain(..drain_count).collect()
+        let reserved = max_txs.saturating_mul(Self::PRIORITY_RESERVED_PERCENT) / 100;
+        let selected: Vec<Transaction> =
+            if (priority.is_empty() || reserved == 0) && policy == DrainPolicy::Fifo {
+                let drain_count = max_txs.min(pool.len());
+                pool.drain(..drain_count).collect()
+            } else {
+                Self::select_batch(&mut pool, &priority, policy, max_txs, reserved)
+            };
+
+        let mut bytes = 0usize;
+        let mut batch = Vec::with_capacity(selected.len());
+        let mut overflow = Vec::new();
+        let mut dropped = self.dropped.write();
+        for tx in selected {
+            let len = tx.encoded_len();
+            if len > max_bytes {
+                dropped.insert(
+                    tx.tx_hash.clone(),
+                    format!("Too large for a block: {len} > {max_bytes} bytes"),
+                );
+            } else if bytes + len <= max_bytes {
+                bytes += len;
+                batch.push(tx);
+            } else {
+                overflow.push(tx);
+            }
+        }
+        pool.splice(0..0, overflow);
+        batch
+    }
+
+    fn select_batch(
+        pool: &mut Vec<Transaction>,
+        priority: &HashSet<String>,
+        policy: DrainPolicy,
+        max_txs: usize,
+        reserved: usize,
+    ) -> Vec<Transaction> {
+        let mut selected = Vec::new();
+        let mut remaining = Vec::with_capacity(pool.len());
+        for tx in pool.drain(..) {
+            if selected.len() < reserved && priority.contains(&tx.sender) {
+                selected.push(tx);
+            } else {
+                remaining.push(tx);
+            }
+        }
+        let fill = max_txs.saturating_sub(selected.len());
+        match policy {
+            DrainPolicy::Fifo => {
+                let fill = fill.min(remaining.len());
+                selected.extend(remaining.drain(..fill));
+            }
+            DrainPolicy::RoundRobin => selected.extend(take_round_robin(&mut remaining, fill)),
+        }
+        *pool = remaining;
+        selected
+    }
+
+    /// Drop pending transactions whose blockhash has left the validity window;
+    /// they can never be included. Returns the number removed.
+    pub fn gc_expired_blockhashes(&self) -> usize {
+        let recent: HashSet<String> = self
+            .chain
+            .read()
+            .iter()
+            .rev()
+            .take(Self::RECENT_BLOCKHASH_WINDOW)
+            .map(|b| b.hash.clone())
+            .collect();
+        let mut pool = self.mempool.write();
+        let mut dropped = self.dropped.write();
+        let before = pool.len();
+        pool.retain(|tx| match &tx.recent_blockhash {
+            Some(hash) if !recent.contains(hash) => {
+                dropped.insert(tx.tx_hash.clone(), format!("Blockhash expired: {hash}"));
+                false
+            }
+            _ => true,
+        });
+        before - pool.len()
+    }
+
+    /// Summaries of up to `limit` pending transactions in inclusion order
+    pub fn mempool_snapshot(&self, limit: usize) -> Vec<MempoolEntry> {
+        let now = Utc::now().timestamp();
+        self.mempool
+            .read()
+            .iter()
+            .take(limit)
+            .enumerate()
+            .map(|(position, tx)| MempoolEntry {
+                tx_hash: tx.tx_hash.clone(),
+                tx_type: tx.tx_type.clone(),
+                sender: tx.sender.clone(),
+                gas_fee: tx.gas_fee,
+                nonce: tx.nonce,
+                position: position as u64,
+                age_seconds: (now - tx.timestamp).max(0) as u64,
+            })
+            .collect()
+    }
+
+    /// Where a transaction is in its lifecycle; None if this node never saw
+    /// it, or dropped it so long ago (more than MAX_DROPPED_TXS drops back)
+    /// that the reason has been forgotten
+    pub fn transaction_status(&self, tx_hash: &str) -> Option<TxStatus> {
+        if self.mempool.read().iter().any(|tx| tx.tx_hash == tx_hash) {
+            return Some(TxStatus::Pending);
+        }
+        if let Some(block_height) = self.transaction_height(tx_hash) {
+            return Some(TxStatus::Confirmed { block_height });
+        }
+        self.dropped
+            .read()
+            .reason(tx_hash)
+            .map(|reason| TxStatus::Dropped {
+                reason: reason.to_string(),
+            })
+    }
+
+    /// Height of the committed block containing `tx_hash`
+    pub fn transaction_height(&self, tx_hash: &str) -> Option<u64> {
+        self.tx_index.read().get(tx_hash).copied()
     }
 
     pub fn get_block_by_height(&self, height: u64) -> Option<Block> {
@@ -470,14 +982,111 @@ impl Blockchain {
     }
 
     pub fn get_transaction(&self, tx_hash: &str) -> Option<Transaction> {
-        for block in self.chain.read().iter() {
-            for tx in &block.transactions {
-                if tx.tx_hash == tx_hash {
-                    return Some(tx.clone());
+        let height = self.transaction_height(tx_hash)?;
+        self.chain
+            .read()
+            .get(height as usize)?
+            .transactions
+            .iter()
+            .find(|tx| tx.tx_hash == tx_hash)
+            .cloned()
+    }
+
+    /// Export blocks `start..=end` as a self-describing archive:
+    /// `[magic][version u32][count u64]` then `[len u32][block json]` per block,
+    /// all integers big-endian. Returns the number of blocks written.
+    pub fn export_range<W: Write>(
+        &self,
+        start: u64,
+        end: u64,
+        mut writer: W,
+    ) -> Result<u64, String> {
+        let chain = self.chain.read();
+        if start > end || end >= chain.len() as u64 {
+            return Err(format!(
+                "Invalid export range {start}..={end} (height {})",
+                chain.len() as u64 - 1
+            ));
+        }
+
+        let count = end - start + 1;
+        let io_err = |e: std::io::Error| format!("Archive write failed: {e}");
+        writer.write_all(LEDGER_ARCHIVE_MAGIC).map_err(io_err)?;
+        writer
+            .write_all(&LEDGER_ARCHIVE_VERSION.to_be_bytes())
+            .map_err(io_err)?;
+        writer.write_all(&count.to_be_bytes()).map_err(io_err)?;
+
+        for block in &chain[start as usize..=end as usize] {
+            let bytes = serde_json::to_vec(block).map_err(|e| e.to_string())?;
+            writer
+                .write_all(&(bytes.len() as u32).to_be_bytes())
+                .map_err(io_err)?;
+            writer.write_all(&bytes).map_err(io_err)?;
+        }
+        writer.flush().map_err(io_err)?;
+        Ok(count)
+    }
+
+    /// Import an archive produced by `export_range`. Each block must carry a
+    /// valid header hash, transactions matching that header, and extend the
+    /// current tip; blocks already present
+    /// with the same hash are skipped. Returns the number of blocks appended.
+    pub fn import_range<R: Read>(&self, mut reader: R) -> Result<u64, String> {
+        let io_err = |e: std::io::Error| format!("Archive read failed: {e}");
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(io_err)?;
+        if &magic != LEDGER_ARCHIVE_MAGIC {
+            return Err("Not a Pecu ledger archive".to_string());
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version).map_err(io_err)?;
+        let version = u32::from_be_bytes(version);
+        if version != LEDGER_ARCHIVE_VERSION {
+            return Err(format!("Unsupported archive version {version}"));
+        }
+        let mut count = [0u8; 8];
+        reader.read_exact(&mut count).map_err(io_err)?;
+        let count = u64::from_be_bytes(count);
+
+        let mut appended = 0;
+        for _ in 0..count {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len).map_err(io_err)?;
+            let len = u32::from_be_bytes(len) as usize;
+            if len > MAX_ARCHIVED_BLOCK_BYTES {
+                return Err(format!(
+                    "Archived block too large: {len} > {MAX_ARCHIVED_BLOCK_BYTES} bytes"
+                ));
+            }
+            let mut bytes = vec![0u8; len];
+            reader.read_exact(&mut bytes).map_err(io_err)?;
+            let block: Block =
+                serde_json::from_slice(&bytes).map_err(|e| format!("Corrupt block: {e}"))?;
+
+            if block.header.compute_hash() != block.hash {
+                return Err(format!("Block {} hash mismatch", block.header.height));
+            }
+            if !block.verify_body() {
+                return Err(format!(
+                    "Block {} transactions do not match its header",
+                    block.header.height
+                ));
+            }
+            if let Some(existing) = self.get_block_by_height(block.header.height) {
+                if existing.hash != block.hash {
+                    return Err(format!(
+                        "Block {} conflicts with local chain",
+                        block.header.height
+                    ));
                 }
+                continue;
             }
+
+            self.commit_block(block)?;
+            appended += 1;
         }
-        None
+        Ok(appended)
     }
 
     /// ERC-20 style: approve spender for contract
@@ -517,6 +1126,227 @@ impl Default for Blockchain {
     }
 }
 
+// ─── Staged State ─────────────────────────────────────────────────────────────
+// Copy-on-write view over balances/nonces used while applying a block, so a
+// failing transaction can be rolled back without touching committed state.
+
+const NULL_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+struct StagedState<'a> {
+    base_balances: &'a HashMap<String, u128>,
+    base_nonces: &'a HashMap<String, u64>,
+    balances: HashMap<String, u128>,
+    nonces: HashMap<String, u64>,
+    burned: u128,
+}
+
+impl<'a> StagedState<'a> {
+    fn new(
+        base_balances: &'a HashMap<String, u128>,
+        base_nonces: &'a HashMap<String, u64>,
+        burned: u128,
+    ) -> Self {
+        StagedState {
+            base_balances,
+            base_nonces,
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+            burned,
+        }
+    }
+
+    fn balance(&self, address: &str) -> u128 {
+        self.balances
+            .get(address)
+            .or_else(|| self.base_balances.get(address))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn credit(&mut self, address: &str, amount: u128) {
+        let bal = self.balance(address).saturating_add(amount);
+        self.balances.insert(address.to_string(), bal);
+    }
+
+    fn debit(&mut self, address: &str, amount: u128) -> Result<(), String> {
+        let bal = self.balance(address);
+        if bal < amount {
+            return Err(format!("Insufficient balance: {bal} < {amount}"));
+        }
+        self.balances.insert(address.to_string(), bal - amount);
+        Ok(())
+    }
+
+    fn bump_nonce(&mut self, address: &str) {
+        let nonce = self
+            .nonces
+            .get(address)
+            .or_else(|| self.base_nonces.get(address))
+            .copied()
+            .unwrap_or(0);
+        self.nonces.insert(address.to_string(), nonce + 1);
+    }
+
+    /// Apply one transaction. Every fallible step (a debit) runs before
+    /// anything is credited, so on error the staged state is unchanged and
+    /// later transactions can still be applied on top of it.
+    fn apply(&mut self, tx: &Transaction, validator: &str) -> Result<(), String> {
+        match tx.tx_type {
+            TransactionType::Transfer | TransactionType::Escrow => {
+                if tx.sender != NULL_ADDRESS {
+                    self.debit(&tx.sender, tx.amount.saturating_add(tx.gas_fee))?;
+                }
+                self.credit(&tx.receiver, tx.amount);
+
+                // Burn 50% of gas fees, validator gets the other 50%
+                self.burned += tx.burned_amount();
+                self.credit(validator, tx.gas_fee - tx.burned_amount());
+            }
+            TransactionType::ValidatorReward => {
+                self.credit(&tx.receiver, tx.amount);
+            }
+            TransactionType::Burn => {
+                self.debit(&tx.sender, tx.amount)?;
+                self.burned += tx.amount;
+            }
+            TransactionType::ERC20Approve => {
+                // allowance handled by token layer
+            }
+            _ => {
+                // Token and contract txs handled by token/escrow layers
+            }
+        }
+
+        if tx.sender != NULL_ADDRESS {
+            self.bump_nonce(&tx.sender);
+        }
+        Ok(())
+    }
+
+    fn into_parts(self) -> (HashMap<String, u128>, HashMap<String, u64>, u128) {
+        (self.balances, self.nonces, self.burned)
+    }
+}
+
+/// Lightweight view of a pending transaction (no notes or call data)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    pub tx_hash: String,
+    pub tx_type: TransactionType,
+    pub sender: String,
+    pub gas_fee: u128,
+    pub nonce: u64,
+    /// Position in the queue; lower is drained into a block first
+    pub position: u64,
+    pub age_seconds: u64,
+}
+
+/// Order in which drain_mempool fills a block after the priority reserve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DrainPolicy {
+    /// Strict arrival order
+    #[default]
+    Fifo,
+    /// One tx per sender per round, senders in order of first arrival, so a
+    /// flooding sender cannot fill every block
+    RoundRobin,
+}
+
+impl std::str::FromStr for DrainPolicy {
+    type Err = String;
+
+    /// Parse a PECU_DRAIN_POLICY value: `fifo` or `round-robin`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fifo" => Ok(DrainPolicy::Fifo),
+            "round-robin" | "roundrobin" => Ok(DrainPolicy::RoundRobin),
+            other => Err(format!("Unknown drain policy: {other}")),
+        }
+    }
+}
+
+/// Remove up to `n` txs from `pending`, taking one per sender per round.
+/// Each sender's own txs keep their relative (nonce) order.
+fn take_round_robin(pending: &mut Vec<Transaction>, n: usize) -> Vec<Transaction> {
+    let picked: Vec<usize> = {
+        let mut senders: Vec<&str> = Vec::new();
+        let mut queues: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (i, tx) in pending.iter().enumerate() {
+            queues
+                .entry(tx.sender.as_str())
+                .or_insert_with(|| {
+                    senders.push(tx.sender.as_str());
+                    VecDeque::new()
+                })
+                .push_back(i);
+        }
+        let mut picked = Vec::new();
+        while picked.len() < n {
+            let before = picked.len();
+            for sender in &senders {
+                if picked.len() == n {
+                    break;
+                }
+                if let Some(i) = queues.get_mut(sender).and_then(|q| q.pop_front()) {
+                    picked.push(i);
+                }
+            }
+            if picked.len() == before {
+                break;
+            }
+        }
+        picked
+    };
+
+    let mut slots: Vec<Option<Transaction>> = pending.drain(..).map(Some).collect();
+    let taken = picked.iter().filter_map(|&i| slots[i].take()).collect();
+    pending.extend(slots.into_iter().flatten());
+    taken
+}
+
+/// Lifecycle of a submitted transaction. Blocks are final once committed,
+/// so Confirmed is also finalized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxStatus {
+    Pending,
+    Confirmed { block_height: u64 },
+    Dropped { reason: String },
+}
+
+/// Reasons for the most recent MAX_DROPPED_TXS dropped transactions; the
+/// oldest entry is evicted first
+#[derive(Debug, Default)]
+pub struct DroppedTxs {
+    reasons: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+impl DroppedTxs {
+    pub fn insert(&mut self, tx_hash: String, reason: String) {
+        if self.reasons.insert(tx_hash.clone(), reason).is_some() {
+            return;
+        }
+        self.order.push_back(tx_hash);
+        while self.order.len() > Blockchain::MAX_DROPPED_TXS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.reasons.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn reason(&self, tx_hash: &str) -> Option<&str> {
+        self.reasons.get(tx_hash).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainStats {
     pub block_height: u64,
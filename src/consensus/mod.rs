@@ -23,11 +23,13 @@
 // "Validators receive randomized rewards ranging from 0.25 to 1.5 PECU per
 // 24-hour period per hosted node."
 
+use crate::chain::Blockchain;
 use crate::crypto;
 pub use crate::crypto::VdfProof;
 use chrono::Utc;
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 // ─── Constants (from whitepaper) ─────────────────────────────────────────────
 
@@ -41,6 +43,9 @@ pub const MAX_DAILY_REWARD: u128 = 55_000_000_000_000_000_000u128;
 /// PoT VDF delay steps (adjustable for network speed vs security)
 pub const POT_DELAY_STEPS: u64 = 100;
 
+/// A validator unseen for longer than one reward period is delinquent
+pub const DELINQUENT_AFTER_SECONDS: i64 = 86400;
+
 // ─── Validator ────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +96,11 @@ impl Validator {
         self.last_seen = Utc::now().timestamp();
     }
 
+    /// Offline, or no heartbeat within DELINQUENT_AFTER_SECONDS of `now`
+    pub fn is_delinquent(&self, now: i64) -> bool {
+        !self.is_online || now - self.last_seen > DELINQUENT_AFTER_SECONDS
+    }
+
     pub fn add_reward(&mut self, amount: u128) {
         self.total_rewards_earned += amount;
         self.blocks_validated += 1;
@@ -171,6 +181,67 @@ impl HalvingSchedule {
     }
 }
 
+// ─── Block Pacing ─────────────────────────────────────────────────────────────
+
+/// When the block producer seals a block: as soon as it is full, otherwise
+/// at the block-time deadline if anything is pending. Size caps are held
+/// within the chain's hard block limits.
+#[derive(Debug, Clone)]
+pub struct BlockPacing {
+    pub block_time: Duration,
+    pub max_block_txs: usize,
+    pub max_block_bytes: usize,
+}
+
+impl BlockPacing {
+    /// Read PECU_BLOCK_TIME_MS, PECU_MAX_BLOCK_TXS and PECU_MAX_BLOCK_BYTES,
+    /// falling back to defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let block_time = std::env::var("PECU_BLOCK_TIME_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(default.block_time);
+        let max_block_txs = std::env::var("PECU_MAX_BLOCK_TXS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(default.max_block_txs)
+            .min(Blockchain::MAX_BLOCK_TXS);
+        let max_block_bytes = std::env::var("PECU_MAX_BLOCK_BYTES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(default.max_block_bytes)
+            .min(Blockchain::MAX_BLOCK_BYTES);
+        BlockPacing {
+            block_time,
+            max_block_txs,
+            max_block_bytes,
+        }
+    }
+
+    pub fn should_seal(&self, pending: usize, since_last_block: Duration) -> bool {
+        pending >= self.max_block_txs || (pending > 0 && since_last_block >= self.block_time)
+    }
+
+    /// How often the producer checks the mempool
+    pub fn poll_interval(&self) -> Duration {
+        (self.block_time / 10).max(Duration::from_millis(10))
+    }
+}
+
+impl Default for BlockPacing {
+    fn default() -> Self {
+        BlockPacing {
+            block_time: Duration::from_secs(2),
+            max_block_txs: 1000,
+            max_block_bytes: 1024 * 1024,
+        }
+    }
+}
+
 // ─── Proof of Time Engine ─────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -207,27 +278,98 @@ impl ProofOfTime {
         self.validators.push(validator);
     }
 
+    /// Register or update a validator's PoS stake; returns its node id
+    pub fn set_stake(&mut self, wallet_address: &str, stake: u128) -> String {
+        match self
+            .validators
+            .iter_mut()
+            .find(|v| v.wallet_address == wallet_address)
+        {
+            Some(v) => {
+                v.stake = stake;
+                v.node_id.clone()
+            }
+            None => {
+                let validator = Validator::new(wallet_address, stake);
+                let node_id = validator.node_id.clone();
+                self.validators.push(validator);
+                node_id
+            }
+        }
+    }
+
+    /// Remove a validator from the set. Its stake leaves the total and it is
+    /// no longer picked as lead from the next block on.
+    pub fn deregister_validator(&mut self, wallet_address: &str) -> Option<Validator> {
+        let idx = self
+            .validators
+            .iter()
+            .position(|v| v.wallet_address == wallet_address)?;
+        Some(self.validators.remove(idx))
+    }
+
+    /// Refresh the liveness of the validator that sealed a block. Time since
+    /// its last sign of life counts as uptime unless it had gone delinquent.
+    /// Returns false if `wallet_address` is not registered.
+    pub fn record_block_sealed(&mut self, wallet_address: &str) -> bool {
+        let now = Utc::now().timestamp();
+        match self
+            .validators
+            .iter_mut()
+            .find(|v| v.wallet_address == wallet_address)
+        {
+            Some(v) => {
+                let gap = now - v.last_seen;
+                let credited = if (0..=DELINQUENT_AFTER_SECONDS).contains(&gap) {
+                    gap as u64
+                } else {
+                    0
+                };
+                v.record_heartbeat(credited);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Total PoS stake across all registered validators
+    pub fn total_stake(&self) -> u128 {
+        self.validators
+            .iter()
+            .fold(0, |total, v| total.saturating_add(v.stake))
+    }
+
     pub fn online_validators(&self) -> Vec<&Validator> {
         self.validators.iter().filter(|v| v.is_online).collect()
     }
 
-    /// Generate a PoT proof for the next block
+    /// Generate a PoT proof for the next block. The lead that seals it is
+    /// chosen separately and deterministically by `leader_for_height`.
     /// Whitepaper: "a Validator node is assigned as the lead at any given moment
     /// to generate a Proof of Time sequence"
-    pub fn generate_pot_proof(&mut self, block_hash_seed: &str) -> (VdfProof, String) {
+    pub fn generate_pot_proof(&mut self, block_hash_seed: &str) -> VdfProof {
         let seed = format!("{block_hash_seed}_{}", self.pot_sequence);
         let proof = crypto::compute_vdf(&seed, POT_DELAY_STEPS);
         self.pot_sequence += 1;
+        proof
+    }
 
-        // Select lead validator using PoT+PoS weighted random selection (BFT)
-        let lead = self.select_lead_validator();
-        (proof, lead)
+    /// Deterministic lead for the block at `height` on top of `previous_hash`.
+    ///
+    /// Anti-grinding: the seed is the already-committed parent hash and the
+    /// height, never the proposer's own PoT seed, so a proposer cannot retry
+    /// proofs until it is picked. Every node derives the same lead from the
+    /// same chain history and validator set.
+    pub fn leader_for_height(&self, previous_hash: &str, height: u64) -> String {
+        let digest = crypto::sha256(format!("{previous_hash}:{height}").as_bytes());
+        let word = u64::from_str_radix(&digest[..16], 16).unwrap_or(0);
+        let fraction = word as f64 / (u64::MAX as f64 + 1.0);
+        self.weighted_pick(fraction)
     }
 
-    /// Hybrid PoT+PoS validator selection with BFT guarantees
-    /// - Computes weighted selection based on uptime (time) + stake (PoS)
-    /// - Uses randomization to prevent centralization
-    fn select_lead_validator(&mut self) -> String {
+    /// Hybrid PoT+PoS validator selection: walk online validators by
+    /// selection weight (uptime + stake) to the given fraction of the total
+    fn weighted_pick(&self, fraction: f64) -> String {
         let online: Vec<&Validator> = self.online_validators();
         if online.is_empty() {
             return "no_validators".to_string();
@@ -238,8 +380,7 @@ impl ProofOfTime {
             return online[0].wallet_address.clone();
         }
 
-        let mut rng = thread_rng();
-        let pick = rng.gen_range(0.0..total_weight);
+        let pick = fraction * total_weight;
         let mut cumulative = 0.0;
 
         for v in &online {
@@ -111,6 +111,40 @@ pub fn public_key_to_pecu_address(public_key: &str) -> String {
     bs58::encode(&hash).into_string()
 }
 
+/// Decode a native Pecu address back to its 32-byte hash, rejecting
+/// invalid Base58 and wrong lengths
+pub fn decode_pecu_address(address: &str) -> Result<[u8; 32], String> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Invalid Base58 address: {e}"))?;
+    <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("Pecu address must be 32 bytes, got {}", bytes.len()))
+}
+
+/// True for a 0x-prefixed, 20-byte hex EVM address
+pub fn is_evm_address(address: &str) -> bool {
+    address
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// ─── Program-Derived Address (CREATE2-style) ─────────────────────────────────
+// Deterministic address for a program id + seeds, with no private key behind
+// it. The 0xff prefix keeps the preimage disjoint from keypair addresses
+// (which hash the bare public key), and seeds are length-prefixed so that
+// ["ab", "c"] and ["a", "bc"] derive different addresses.
+
+pub fn derive_program_address(program_id: &str, seeds: &[&[u8]]) -> String {
+    let mut preimage = vec![0xffu8];
+    preimage.extend_from_slice(program_id.as_bytes());
+    for seed in seeds {
+        preimage.extend_from_slice(&(seed.len() as u32).to_be_bytes());
+        preimage.extend_from_slice(seed);
+    }
+    let hash = keccak256_bytes(&preimage);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
 // ─── Block Address ────────────────────────────────────────────────────────────
 // Whitepaper: "Communication / Transaction Information's Hashed with SHA512"
 
@@ -208,6 +242,20 @@ pub fn cbc_encrypt(data: &[u8], key: &[u8], iv: &[u8]) -> Vec<u8> {
     result
 }
 
+// ─── Signing domains ─────────────────────────────────────────────────────────
+// Every signed payload is prefixed with a tag naming its message type, so a
+// signature produced in one context never verifies in another
+
+pub const DOMAIN_TRANSACTION: &str = "pecu-novus/transaction/v1";
+pub const DOMAIN_MESSAGE: &str = "pecu-novus/message/v1";
+pub const DOMAIN_VALIDATOR: &str = "pecu-novus/validator/v1";
+
+/// Signing preimage for `data` in `domain`; tags contain no NUL, so the
+/// boundary between tag and data is unambiguous
+pub fn domain_separated(domain: &str, data: &str) -> String {
+    format!("{domain}\0{data}")
+}
+
 /// Sign a hash using the Proof of Time sequence (replication identity key)
 pub fn sign_with_pot_sequence(data: &str, pot_proof: &VdfProof) -> String {
     let combined = format!("{}{}{}", data, pot_proof.output, pot_proof.sequence_count);
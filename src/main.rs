@@ -23,17 +23,18 @@ mod storage;
 mod tokens;
 mod wallet;
 
-use chain::{Blockchain, Transaction, TransactionType};
-use consensus::{HalvingSchedule, ProofOfTime, Validator, VestingSchedule};
+use chain::{Blockchain, DrainPolicy, Transaction, TransactionType};
+use consensus::{BlockPacing, HalvingSchedule, ProofOfTime, Validator, VestingSchedule};
 use escrow::MVault;
-use rpc::{AppState, RpcServer};
+use rpc::{AppState, RpcLimits, RpcServer};
+use storage::ChainStorage;
 use tokens::{AssetClass, PNP16Token, TokenRegistry};
 use wallet::Wallet;
 
 use chrono::Utc;
 use parking_lot::RwLock;
 use std::sync::Arc;
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
 use tracing::{info, warn};
 
 #[tokio::main]
@@ -44,7 +45,37 @@ async fn main() {
         .init();
     print_banner();
 
-    let state = AppState::new();
+    let mut state = AppState::new();
+    state.faucet_enabled = std::env::var("PECU_FAUCET").is_ok_and(|v| v == "1" || v == "true");
+    if state.faucet_enabled {
+        warn!("Faucet enabled: pecu_requestAirdrop pays out of the Foundation account");
+    }
+    if let Ok(list) = std::env::var("PECU_PRIORITY_SENDERS") {
+        let count = state.blockchain.set_priority_senders(&list);
+        info!("Priority senders: {count} address(es) drained first");
+    }
+    if let Ok(value) = std::env::var("PECU_DRAIN_POLICY") {
+        match value.parse::<DrainPolicy>() {
+            Ok(policy) => {
+                *state.blockchain.drain_policy.write() = policy;
+                info!("Mempool drain policy: {policy:?}");
+            }
+            Err(e) => warn!("{e}; keeping {:?}", *state.blockchain.drain_policy.read()),
+        }
+    }
+    let store = match std::env::var("PECU_DATA_DIR") {
+        Ok(path) => match ChainStorage::open(&path) {
+            Ok(store) => {
+                info!("Persisting blocks and account state to {path}");
+                Some(store)
+            }
+            Err(e) => {
+                warn!("Cannot open data dir {path}: {e}; running in memory only");
+                None
+            }
+        },
+        Err(_) => None,
+    };
     seed_demo_data(&state);
     print_startup_summary(&state);
 
@@ -52,31 +83,54 @@ async fn main() {
     let pot_clone = Arc::clone(&state.pot);
 
     // Background block producer
+    let pacing = BlockPacing::from_env();
     tokio::spawn(async move {
-        info!("Block producer started (PoT interval: 2s)");
+        info!(
+            "Block producer started (block time: {}ms, max {} txs / {} bytes)",
+            pacing.block_time.as_millis(),
+            pacing.max_block_txs,
+            pacing.max_block_bytes
+        );
+        let mut last_block = Instant::now();
+        let mut persisted = 0;
         loop {
-            sleep(Duration::from_secs(2)).await;
-            let txs = bc_clone.drain_mempool(1000);
+            sleep(pacing.poll_interval()).await;
+            let pending = bc_clone.mempool.read().len();
+            if !pacing.should_seal(pending, last_block.elapsed()) {
+                continue;
+            }
+            let expired = bc_clone.gc_expired_blockhashes();
+            if expired > 0 {
+                info!("Dropped {expired} pending txs with an expired blockhash");
+            }
+            let txs = bc_clone.drain_mempool_within(pacing.max_block_txs, pacing.max_block_bytes);
             if txs.is_empty() {
                 continue;
             }
+            last_block = Instant::now();
             let latest = bc_clone.latest_block();
             let seed = format!(
                 "{}_{}",
                 latest.hash,
                 Utc::now().timestamp_nanos_opt().unwrap_or(0)
             );
-            let (proof, validator_addr) = pot_clone.write().generate_pot_proof(&seed);
+            let proof = pot_clone.write().generate_pot_proof(&seed);
             let height = bc_clone.block_height() + 1;
-            let block = chain::Block::new(height, &latest.hash, txs, &validator_addr, proof);
-            let bh = block.hash[..16].to_string();
-            let tc = block.transactions.len();
-            match bc_clone.commit_block(block) {
-                Ok(_) => info!(
-                    "Block #{height} committed | {bh}... | {tc} txs | validator: {validator_addr}"
-                ),
+            let validator_addr = pot_clone.read().leader_for_height(&latest.hash, height);
+            match bc_clone.produce_block(height, &latest.hash, txs, &validator_addr, proof) {
+                Ok(block) => {
+                    pot_clone.write().record_block_sealed(&validator_addr);
+                    info!(
+                        "Block #{height} committed | {}... | {} txs | validator: {validator_addr}",
+                        &block.hash[..16],
+                        block.transactions.len()
+                    )
+                }
                 Err(e) => warn!("Block commit failed: {e}"),
             }
+            if let Some(store) = &store {
+                persist_new_blocks(store, &bc_clone, &mut persisted);
+            }
         }
     });
 
@@ -115,7 +169,7 @@ async fn main() {
         .parse::<u16>()
         .unwrap_or(8545);
 
-    let server = RpcServer::new(state, port);
+    let server = RpcServer::new(state, port).with_limits(RpcLimits::from_env());
     server.run().await;
 }
 
@@ -147,6 +201,29 @@ fn print_startup_summary(state: &AppState) {
     info!("Halving schedule: 2017→20M, 2027→10M, 2037→5M, 2047→2.5M, 2057→1.25M PECU/year");
 }
 
+/// Save blocks committed since `persisted` (including pecu_mineBlock ones),
+/// then flush account state as a single batch
+fn persist_new_blocks(store: &ChainStorage, blockchain: &Blockchain, persisted: &mut u64) {
+    let tip = blockchain.block_height();
+    for height in *persisted + 1..=tip {
+        let saved = match blockchain.get_block_by_height(height) {
+            Some(block) => store.save_block(&block),
+            None => break,
+        };
+        if let Err(e) = saved {
+            warn!("Failed to persist block #{height}: {e}");
+            return;
+        }
+        *persisted = height;
+    }
+    if let Err(e) = store
+        .save_chain_state(blockchain)
+        .and_then(|_| store.flush())
+    {
+        warn!("Failed to persist account state: {e}");
+    }
+}
+
 fn seed_demo_data(state: &AppState) {
     let alice = Wallet::new();
     let bob = Wallet::new();
@@ -30,11 +30,20 @@ use crate::escrow::MVault;
 use crate::tokens::TokenRegistry;
 use crate::wallet::Wallet;
 
-use axum::{extract::State, http::StatusCode, response::Json, routing::post, Router};
+use axum::{
+    extract::{Extension, State},
+    http::{Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::post,
+    Router,
+};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
 
@@ -49,6 +58,12 @@ pub struct AppState {
     pub wallets: Arc<RwLock<std::collections::HashMap<String, Wallet>>>,
     pub chain_id: u64,
     pub network_name: String,
+    /// Devnet faucet: lets pecu_requestAirdrop pay out of the Foundation
+    /// account. Off unless the operator enables it.
+    pub faucet_enabled: bool,
+    /// Slots for pending pecu_waitForBlock calls, kept apart from the
+    /// global request cap so long-polls cannot lock out other clients
+    pub block_waiters: Arc<Semaphore>,
 }
 
 impl AppState {
@@ -61,6 +76,8 @@ impl AppState {
             wallets: Arc::new(RwLock::new(std::collections::HashMap::new())),
             chain_id: 27272727, // Pecu Novus chain ID
             network_name: "Pecu Novus Mainnet".to_string(),
+            faucet_enabled: false,
+            block_waiters: Arc::new(Semaphore::new(MAX_BLOCK_WAITERS)),
         }
     }
 }
@@ -115,34 +132,52 @@ impl RpcResponse {
 
 // ─── RPC Server ───────────────────────────────────────────────────────────────
 
+/// JSON-RPC routes (`/` and `/rpc`) with limits and CORS applied
+pub fn rpc_router(state: Arc<AppState>, limits: &RpcLimits) -> Router {
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_headers(Any)
+        .allow_methods(Any);
+
+    let app = Router::new()
+        .route("/", post(handle_rpc))
+        .route("/rpc", post(handle_rpc));
+    apply_limits(app, limits).layer(cors).with_state(state)
+}
+
 pub struct RpcServer {
     pub state: AppState,
     pub port: u16,
+    pub limits: RpcLimits,
 }
 
 impl RpcServer {
     pub fn new(state: AppState, port: u16) -> Self {
-        RpcServer { state, port }
+        RpcServer {
+            state,
+            port,
+            limits: RpcLimits::default(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: RpcLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     pub async fn run(self) {
-        let state = Arc::new(self.state);
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_headers(Any)
-            .allow_methods(Any);
-
-        let app = Router::new()
-            .route("/", post(handle_rpc))
-            .route("/rpc", post(handle_rpc))
-            .layer(cors)
-            .with_state(state);
+        let app = rpc_router(Arc::new(self.state), &self.limits);
 
         let addr = format!("0.0.0.0:{}", self.port);
         info!("🚀 Pecu Novus RPC Server listening on http://{}", addr);
         info!("   Chain ID: 27272727 | Network: Pecu Novus Mainnet");
         info!("   EVM Compatible: eth_* methods available");
         info!("   Native: pecu_* | pnp16_* | escrow_* methods available");
+        info!(
+            "   Limits: {}ms timeout | {} concurrent requests",
+            self.limits.request_timeout.as_millis(),
+            self.limits.max_concurrent_requests
+        );
 
         axum::Server::bind(&addr.parse().unwrap())
             .serve(app.into_make_service())
@@ -151,89 +186,233 @@ impl RpcServer {
     }
 }
 
+// ─── Request Limits ───────────────────────────────────────────────────────────
+// A slow call (e.g. a large holder scan) must not tie up the server, and load
+// beyond the concurrency cap is shed with 503 instead of queuing unbounded.
+
+#[derive(Debug, Clone)]
+pub struct RpcLimits {
+    pub request_timeout: Duration,
+    pub max_concurrent_requests: usize,
+}
+
+impl RpcLimits {
+    /// Read PECU_RPC_TIMEOUT_MS / PECU_RPC_MAX_CONCURRENT, falling back to defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let request_timeout = std::env::var("PECU_RPC_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(default.request_timeout);
+        let max_concurrent_requests = std::env::var("PECU_RPC_MAX_CONCURRENT")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(default.max_concurrent_requests);
+        RpcLimits {
+            request_timeout,
+            max_concurrent_requests,
+        }
+    }
+}
+
+impl Default for RpcLimits {
+    fn default() -> Self {
+        RpcLimits {
+            request_timeout: Duration::from_secs(30),
+            max_concurrent_requests: 256,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RequestLimiter {
+    permits: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+/// Concurrency permit shared with the handler through request extensions,
+/// so a timed-out request keeps its slot until the dispatch it started ends.
+/// A handler that only awaits (pecu_waitForBlock) may hand it back early.
+#[derive(Clone)]
+struct RequestPermit {
+    slot: Arc<parking_lot::Mutex<Option<OwnedSemaphorePermit>>>,
+}
+
+impl RequestPermit {
+    fn release(&self) {
+        self.slot.lock().take();
+    }
+}
+
+/// Wrap a router with the per-request timeout and global concurrency cap
+pub fn apply_limits<S>(router: Router<S>, limits: &RpcLimits) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let limiter = RequestLimiter {
+        permits: Arc::new(Semaphore::new(limits.max_concurrent_requests)),
+        timeout: limits.request_timeout,
+    };
+    router.layer(middleware::from_fn_with_state(limiter, limit_requests))
+}
+
+async fn limit_requests<B>(
+    State(limiter): State<RequestLimiter>,
+    mut req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let _permit = match limiter.permits.clone().try_acquire_owned() {
+        Ok(permit) => {
+            let permit = RequestPermit {
+                slot: Arc::new(parking_lot::Mutex::new(Some(permit))),
+            };
+            req.extensions_mut().insert(permit.clone());
+            permit
+        }
+        Err(_) => {
+            warn!("RPC request rejected: concurrency limit reached");
+            let body = RpcResponse::err(None, -32000, "Server busy: too many concurrent requests");
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+        }
+    };
+
+    match tokio::time::timeout(limiter.timeout, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!("RPC request timed out after {:?}", limiter.timeout);
+            let message = format!("Request timed out after {}ms", limiter.timeout.as_millis());
+            (
+                StatusCode::OK,
+                Json(RpcResponse::err(None, -32000, &message)),
+            )
+                .into_response()
+        }
+    }
+}
+
 // ─── Main RPC Handler ─────────────────────────────────────────────────────────
 
 async fn handle_rpc(
     State(state): State<Arc<AppState>>,
+    permit: Option<Extension<RequestPermit>>,
     Json(req): Json<RpcRequest>,
 ) -> (StatusCode, Json<RpcResponse>) {
+    // Handlers are synchronous; run them off the reactor so the timeout layer
+    // can still answer the client while a slow call is in progress. The
+    // permit moves into the task: the call still counts against the
+    // concurrency cap after its client has been sent a timeout.
+    let id = req.id.clone();
+    if req.method == "pecu_waitForBlock" {
+        let permit = permit.map(|Extension(p)| p);
+        let params = req.params.unwrap_or(json!([]));
+        let response = pecu_wait_for_block_async(&state, permit, id, &params).await;
+        return (StatusCode::OK, Json(response));
+    }
+    let dispatched = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        dispatch(&state, &req)
+    });
+    match dispatched.await {
+        Ok(response) => (StatusCode::OK, Json(response)),
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(RpcResponse::err(id, -32603, "Internal error")),
+        ),
+    }
+}
+
+/// Route a single JSON-RPC request to its method handler
+pub fn dispatch(state: &AppState, req: &RpcRequest) -> RpcResponse {
     let id = req.id.clone();
     let params = req.params.clone().unwrap_or(json!([]));
 
     info!("RPC call: {}", req.method);
 
-    let response = match req.method.as_str() {
+    match req.method.as_str() {
         // ── EVM / Ethereum-compatible methods ─────────────────────────────────
-        "eth_chainId" => eth_chain_id(&state, id),
-        "net_version" => net_version(&state, id),
-        "eth_blockNumber" => eth_block_number(&state, id),
-        "eth_getBalance" => eth_get_balance(&state, id, &params),
-        "eth_getBlockByNumber" => eth_get_block_by_number(&state, id, &params),
-        "eth_getBlockByHash" => eth_get_block_by_hash(&state, id, &params),
-        "eth_getTransactionByHash" => eth_get_tx_by_hash(&state, id, &params),
-        "eth_sendRawTransaction" => eth_send_raw_transaction(&state, id, &params),
-        "eth_call" => eth_call(&state, id, &params),
-        "eth_gasPrice" => eth_gas_price(&state, id),
-        "eth_estimateGas" => eth_estimate_gas(&state, id, &params),
-        "eth_getTransactionCount" => eth_get_transaction_count(&state, id, &params),
-        "eth_getLogs" => eth_get_logs(&state, id, &params),
-        "web3_clientVersion" => web3_client_version(&state, id),
-        "eth_syncing" => eth_syncing(&state, id),
-        "eth_accounts" => eth_accounts(&state, id),
+        "eth_chainId" => eth_chain_id(state, id),
+        "net_version" => net_version(state, id),
+        "eth_blockNumber" => eth_block_number(state, id),
+        "eth_getBalance" => eth_get_balance(state, id, &params),
+        "eth_getBlockByNumber" => eth_get_block_by_number(state, id, &params),
+        "eth_getBlockByHash" => eth_get_block_by_hash(state, id, &params),
+        "pecu_waitForBlock" => pecu_wait_for_block(state, id, &params),
+        "eth_getTransactionByHash" => eth_get_tx_by_hash(state, id, &params),
+        "eth_getTransactionReceipt" => eth_get_tx_receipt(state, id, &params),
+        "eth_sendRawTransaction" => eth_send_raw_transaction(state, id, &params),
+        "eth_call" => eth_call(state, id, &params),
+        "eth_gasPrice" => eth_gas_price(state, id),
+        "eth_estimateGas" => eth_estimate_gas(state, id, &params),
+        "eth_getTransactionCount" => eth_get_transaction_count(state, id, &params),
+        "eth_getLogs" => eth_get_logs(state, id, &params),
+        "web3_clientVersion" => web3_client_version(state, id),
+        "eth_syncing" => eth_syncing(state, id),
+        "eth_accounts" => eth_accounts(state, id),
 
         // ── ERC-20 token calls (via eth_call ABI dispatch) ────────────────────
-        "erc20_balanceOf" => erc20_balance_of(&state, id, &params),
-        "erc20_transfer" => erc20_transfer(&state, id, &params),
-        "erc20_approve" => erc20_approve(&state, id, &params),
-        "erc20_allowance" => erc20_allowance(&state, id, &params),
-        "erc20_transferFrom" => erc20_transfer_from(&state, id, &params),
-        "erc20_totalSupply" => erc20_total_supply(&state, id, &params),
+        "erc20_balanceOf" => erc20_balance_of(state, id, &params),
+        "erc20_transfer" => erc20_transfer(state, id, &params),
+        "erc20_approve" => erc20_approve(state, id, &params),
+        "erc20_allowance" => erc20_allowance(state, id, &params),
+        "erc20_transferFrom" => erc20_transfer_from(state, id, &params),
+        "erc20_totalSupply" => erc20_total_supply(state, id, &params),
 
         // ── Pecu Novus native methods ─────────────────────────────────────────
-        "pecu_getNetworkInfo" => pecu_get_network_info(&state, id),
-        "pecu_getChainStats" => pecu_get_chain_stats(&state, id),
-        "pecu_sendTransaction" => pecu_send_transaction(&state, id, &params),
-        "pecu_getBalance" => pecu_get_balance(&state, id, &params),
-        "pecu_createWallet" => pecu_create_wallet(&state, id),
-        "pecu_getWallet" => pecu_get_wallet(&state, id, &params),
-        "pecu_getValidators" => pecu_get_validators(&state, id),
-        "pecu_registerValidator" => pecu_register_validator(&state, id, &params),
-        "pecu_getHalvingSchedule" => pecu_get_halving_schedule(&state, id),
-        "pecu_getVestingSchedule" => pecu_get_vesting_schedule(&state, id),
-        "pecu_mineBlock" => pecu_mine_block(&state, id),
-        "pecu_getTokenomics" => pecu_get_tokenomics(&state, id),
+        "pecu_getNetworkInfo" => pecu_get_network_info(state, id),
+        "pecu_getChainStats" => pecu_get_chain_stats(state, id),
+        "pecu_getMempool" => pecu_get_mempool(state, id, &params),
+        "pecu_getLatestBlockhash" => pecu_get_latest_blockhash(state, id),
+        "pecu_sendTransaction" => pecu_send_transaction(state, id, &params),
+        "pecu_getTransactionStatus" => pecu_get_transaction_status(state, id, &params),
+        "pecu_getBalance" => pecu_get_balance(state, id, &params),
+        "pecu_getMultipleAccounts" => pecu_get_multiple_accounts(state, id, &params),
+        "pecu_requestAirdrop" => pecu_request_airdrop(state, id, &params),
+        "pecu_createWallet" => pecu_create_wallet(state, id),
+        "pecu_getWallet" => pecu_get_wallet(state, id, &params),
+        "pecu_getValidators" => pecu_get_validators(state, id),
+        "pecu_getValidatorSet" => pecu_get_validator_set(state, id),
+        "pecu_getLeaderSchedule" => pecu_get_leader_schedule(state, id),
+        "pecu_getPotProof" => pecu_get_pot_proof(state, id, &params),
+        "pecu_registerValidator" => pecu_register_validator(state, id, &params),
+        "pecu_setValidatorStake" => pecu_set_validator_stake(state, id, &params),
+        "pecu_deregisterValidator" => pecu_deregister_validator(state, id, &params),
+        "pecu_getHalvingSchedule" => pecu_get_halving_schedule(state, id),
+        "pecu_getVestingSchedule" => pecu_get_vesting_schedule(state, id),
+        "pecu_mineBlock" => pecu_mine_block(state, id),
+        "pecu_getTokenomics" => pecu_get_tokenomics(state, id),
 
         // ── PNP16 token methods ───────────────────────────────────────────────
-        "pnp16_deployToken" => pnp16_deploy_token(&state, id, &params),
-        "pnp16_listTokens" => pnp16_list_tokens(&state, id),
-        "pnp16_getToken" => pnp16_get_token(&state, id, &params),
-        "pnp16_mint" => pnp16_mint(&state, id, &params),
-        "pnp16_burn" => pnp16_burn(&state, id, &params),
-        "pnp16_transfer" => pnp16_transfer(&state, id, &params),
+        "pnp16_deployToken" => pnp16_deploy_token(state, id, &params),
+        "pnp16_listTokens" => pnp16_list_tokens(state, id),
+        "pnp16_getToken" => pnp16_get_token(state, id, &params),
+        "pnp16_mint" => pnp16_mint(state, id, &params),
+        "pnp16_burn" => pnp16_burn(state, id, &params),
+        "pnp16_transfer" => pnp16_transfer(state, id, &params),
+        "pnp16_getHolders" => pnp16_get_holders(state, id, &params),
 
         // ── Escrow / MVault methods ───────────────────────────────────────────
-        "escrow_create" => escrow_create(&state, id, &params),
-        "escrow_release" => escrow_release(&state, id, &params),
-        "escrow_cancel" => escrow_cancel(&state, id, &params),
-        "escrow_get" => escrow_get(&state, id, &params),
-        "escrow_listByAddress" => escrow_list_by_address(&state, id, &params),
-        "transfercard_create" => transfer_card_create(&state, id, &params),
-        "transfercard_redeem" => transfer_card_redeem(&state, id, &params),
+        "escrow_create" => escrow_create(state, id, &params),
+        "escrow_release" => escrow_release(state, id, &params),
+        "escrow_cancel" => escrow_cancel(state, id, &params),
+        "escrow_get" => escrow_get(state, id, &params),
+        "escrow_listByAddress" => escrow_list_by_address(state, id, &params),
+        "transfercard_create" => transfer_card_create(state, id, &params),
+        "transfercard_redeem" => transfer_card_redeem(state, id, &params),
 
         // ── Cold storage ──────────────────────────────────────────────────────
-        "css_moveToColdStorage" => css_move_to_cold_storage(&state, id, &params),
-        "css_redeemColdStorage" => css_redeem_cold_storage(&state, id, &params),
+        "css_moveToColdStorage" => css_move_to_cold_storage(state, id, &params),
+        "css_redeemColdStorage" => css_redeem_cold_storage(state, id, &params),
 
         // ── Access Keys ───────────────────────────────────────────────────────
-        "gak_connect" => gak_connect(&state, id, &params),
-        "gak_disconnect" => gak_disconnect(&state, id, &params),
-        "dak_register" => dak_register(&state, id, &params),
-        "dak_verifyKyc" => dak_verify_kyc(&state, id, &params),
+        "gak_connect" => gak_connect(state, id, &params),
+        "gak_disconnect" => gak_disconnect(state, id, &params),
+        "dak_register" => dak_register(state, id, &params),
+        "dak_verifyKyc" => dak_verify_kyc(state, id, &params),
 
         method => RpcResponse::err(id, -32601, &format!("Method not found: {method}")),
-    };
-
-    (StatusCode::OK, Json(response))
+    }
 }
 
 // ─── EVM Methods ─────────────────────────────────────────────────────────────
@@ -280,6 +459,93 @@ fn eth_get_block_by_hash(state: &AppState, id: Option<Value>, params: &Value) ->
     }
 }
 
+/// Longest a single pecu_waitForBlock call may block
+pub const MAX_BLOCK_WAIT: Duration = Duration::from_secs(10);
+/// Most pecu_waitForBlock calls that may be pending at once
+pub const MAX_BLOCK_WAITERS: usize = 64;
+const BLOCK_WAIT_POLL: Duration = Duration::from_millis(50);
+
+fn block_wait_params(params: &Value) -> Option<(u64, Duration)> {
+    let height = params[0].as_u64()?;
+    let timeout = params[1]
+        .as_u64()
+        .map(Duration::from_millis)
+        .unwrap_or(MAX_BLOCK_WAIT)
+        .min(MAX_BLOCK_WAIT);
+    Some((height, timeout))
+}
+
+fn block_wait_timed_out(id: Option<Value>, height: u64, timeout: Duration) -> RpcResponse {
+    RpcResponse::err(
+        id,
+        -32000,
+        &format!(
+            "Block {height} not produced within {}ms",
+            timeout.as_millis()
+        ),
+    )
+}
+
+/// Long-poll for the block at `height`: returns it as soon as it is
+/// committed (committed blocks are final), or errors once the timeout passes.
+/// Direct callers block their thread; the HTTP server uses the async variant.
+fn pecu_wait_for_block(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let (height, timeout) = match block_wait_params(params) {
+        Some(p) => p,
+        None => return RpcResponse::err(id, -32602, "Block height required"),
+    };
+    let deadline = std::time::Instant::now() + timeout;
+
+    loop {
+        if let Some(block) = state.blockchain.get_block_by_height(height) {
+            return RpcResponse::ok(id, block_to_eth_json(&block));
+        }
+        if std::time::Instant::now() >= deadline {
+            return block_wait_timed_out(id, height, timeout);
+        }
+        std::thread::sleep(BLOCK_WAIT_POLL);
+    }
+}
+
+/// pecu_waitForBlock over HTTP: waits on the chain's tip signal instead of a
+/// blocking thread, and trades the request's global permit for one of the
+/// MAX_BLOCK_WAITERS slots while it waits
+async fn pecu_wait_for_block_async(
+    state: &AppState,
+    permit: Option<RequestPermit>,
+    id: Option<Value>,
+    params: &Value,
+) -> RpcResponse {
+    let (height, timeout) = match block_wait_params(params) {
+        Some(p) => p,
+        None => return RpcResponse::err(id, -32602, "Block height required"),
+    };
+    let _waiter = match state.block_waiters.clone().try_acquire_owned() {
+        Ok(w) => w,
+        Err(_) => return RpcResponse::err(id, -32000, "Too many pending block waits"),
+    };
+    if let Some(permit) = permit {
+        permit.release();
+    }
+
+    let mut tip = state.blockchain.subscribe_tip();
+    let produced = tokio::time::timeout(timeout, async {
+        loop {
+            if let Some(block) = state.blockchain.get_block_by_height(height) {
+                return Some(block);
+            }
+            if tip.changed().await.is_err() {
+                return None;
+            }
+        }
+    })
+    .await;
+    match produced {
+        Ok(Some(block)) => RpcResponse::ok(id, block_to_eth_json(&block)),
+        _ => block_wait_timed_out(id, height, timeout),
+    }
+}
+
 fn eth_get_tx_by_hash(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     let hash = params[0].as_str().unwrap_or("");
     match state.blockchain.get_transaction(hash) {
@@ -288,6 +554,49 @@ fn eth_get_tx_by_hash(state: &AppState, id: Option<Value>, params: &Value) -> Rp
     }
 }
 
+/// Receipt for a committed transaction; null while pending or if unknown.
+/// Blocks are applied all-or-nothing, so every committed tx succeeded.
+fn eth_get_tx_receipt(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let hash = params[0].as_str().unwrap_or("");
+    let block = match state
+        .blockchain
+        .transaction_height(hash)
+        .and_then(|h| state.blockchain.get_block_by_height(h))
+    {
+        Some(block) => block,
+        None => return RpcResponse::ok(id, Value::Null),
+    };
+    let (index, tx) = match block
+        .transactions
+        .iter()
+        .enumerate()
+        .find(|(_, tx)| tx.tx_hash == hash)
+    {
+        Some(found) => found,
+        None => return RpcResponse::ok(id, Value::Null),
+    };
+    RpcResponse::ok(
+        id,
+        json!({
+            "transactionHash": tx.tx_hash,
+            "transactionIndex": format!("0x{:x}", index),
+            "blockHash": block.hash,
+            "blockNumber": format!("0x{:x}", block.header.height),
+            "from": tx.sender,
+            "to": tx.receiver,
+            "status": "0x1",
+            // Gas figures match eth_estimateGas / eth_gasPrice; the PECU
+            // actually charged is `fee`
+            "gasUsed": GAS_PER_TX,
+            "effectiveGasPrice": GAS_PRICE,
+            "fee": format!("0x{:x}", tx.gas_fee),
+            "burned": format!("0x{:x}", tx.burned_amount()),
+            "contractAddress": tx.contract_address,
+            "logs": []
+        }),
+    )
+}
+
 fn eth_send_raw_transaction(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     // Accept hex-encoded JSON transaction for compatibility
     let raw = params[0].as_str().unwrap_or("");
@@ -314,14 +623,19 @@ fn eth_call(_state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse
     RpcResponse::ok(id, json!("0x"))
 }
 
+/// Symbolic gas price reported to EVM tooling: 1 gwei
+const GAS_PRICE: &str = "0x3B9ACA00";
+/// Gas units reported for every transaction: the 21000 standard transfer
+const GAS_PER_TX: &str = "0x5208";
+
 fn eth_gas_price(_state: &AppState, id: Option<Value>) -> RpcResponse {
     // Flat fee rate: 0.0025% — return as gwei equivalent
-    RpcResponse::ok(id, json!("0x3B9ACA00")) // 1 gwei symbolic
+    RpcResponse::ok(id, json!(GAS_PRICE))
 }
 
 fn eth_estimate_gas(_state: &AppState, id: Option<Value>, _params: &Value) -> RpcResponse {
     // Pecu uses flat gas fee, so estimate is always the same
-    RpcResponse::ok(id, json!("0x5208")) // 21000 standard
+    RpcResponse::ok(id, json!(GAS_PER_TX))
 }
 
 fn eth_get_transaction_count(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
@@ -467,6 +781,69 @@ fn pecu_get_chain_stats(state: &AppState, id: Option<Value>) -> RpcResponse {
     RpcResponse::ok(id, serde_json::to_value(stats).unwrap_or(json!({})))
 }
 
+fn pecu_get_latest_blockhash(state: &AppState, id: Option<Value>) -> RpcResponse {
+    use crate::chain::Blockchain;
+
+    let latest = state.blockchain.latest_block();
+    RpcResponse::ok(
+        id,
+        json!({
+            "blockhash": latest.hash,
+            "height": latest.header.height,
+            "lastValidHeight": latest.header.height + Blockchain::RECENT_BLOCKHASH_WINDOW as u64 - 1
+        }),
+    )
+}
+
+fn pecu_get_transaction_status(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    use crate::chain::TxStatus;
+
+    let hash = params[0].as_str().unwrap_or("");
+    let status = match state.blockchain.transaction_status(hash) {
+        Some(TxStatus::Pending) => json!({ "status": "pending" }),
+        Some(TxStatus::Confirmed { block_height }) => json!({
+            "status": "confirmed",
+            "blockHeight": block_height
+        }),
+        Some(TxStatus::Dropped { reason }) => json!({
+            "status": "dropped",
+            "reason": reason
+        }),
+        None => Value::Null,
+    };
+    RpcResponse::ok(id, status)
+}
+
+/// Server-side cap on entries returned by pecu_getMempool
+pub const MAX_MEMPOOL_ENTRIES: usize = 1000;
+
+fn pecu_get_mempool(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let limit = (params[0].as_u64().unwrap_or(100) as usize).min(MAX_MEMPOOL_ENTRIES);
+    let entries: Vec<Value> = state
+        .blockchain
+        .mempool_snapshot(limit)
+        .iter()
+        .map(|e| {
+            json!({
+                "txHash": e.tx_hash,
+                "type": format!("{:?}", e.tx_type),
+                "from": e.sender,
+                "gasFee": e.gas_fee.to_string(),
+                "nonce": e.nonce,
+                "position": e.position,
+                "ageSeconds": e.age_seconds
+            })
+        })
+        .collect();
+    RpcResponse::ok(
+        id,
+        json!({
+            "pending": state.blockchain.mempool.read().len(),
+            "transactions": entries
+        }),
+    )
+}
+
 fn pecu_send_transaction(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     let sender = params[0].as_str().unwrap_or("");
     let receiver = params[1].as_str().unwrap_or("");
@@ -476,9 +853,10 @@ fn pecu_send_transaction(state: &AppState, id: Option<Value>, params: &Value) ->
         .parse::<u128>()
         .unwrap_or(0);
     let note = params[3].as_str().map(|s| s.to_string());
+    let recent_blockhash = params[4].as_str();
     let nonce = state.blockchain.get_nonce(sender);
 
-    let tx = Transaction::new(
+    let mut tx = Transaction::new(
         TransactionType::Transfer,
         sender,
         receiver,
@@ -490,6 +868,9 @@ fn pecu_send_transaction(state: &AppState, id: Option<Value>, params: &Value) ->
         None,
         nonce,
     );
+    if let Some(blockhash) = recent_blockhash {
+        tx = tx.with_recent_blockhash(blockhash);
+    }
     let hash = tx.tx_hash.clone();
 
     match state.blockchain.add_to_mempool(tx) {
@@ -498,6 +879,49 @@ fn pecu_send_transaction(state: &AppState, id: Option<Value>, params: &Value) ->
     }
 }
 
+/// Account that funds pecu_requestAirdrop (holds the genesis supply)
+pub const FAUCET_ACCOUNT: &str = "PecuNovusFoundation";
+
+/// Largest single airdrop: 1,000 PECU
+pub const MAX_AIRDROP: u128 = 1_000 * Wallet::UNIT_FACTOR;
+
+fn pecu_request_airdrop(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    if !state.faucet_enabled {
+        return RpcResponse::err(id, -32000, "Faucet is disabled on this node");
+    }
+    let receiver = params[0].as_str().unwrap_or("");
+    let amount = params[1]
+        .as_str()
+        .unwrap_or("0")
+        .parse::<u128>()
+        .unwrap_or(0);
+    if receiver.is_empty() || amount == 0 || amount > MAX_AIRDROP {
+        return RpcResponse::err(
+            id,
+            -32602,
+            &format!("Airdrop needs an address and an amount of 1..={MAX_AIRDROP}"),
+        );
+    }
+
+    let nonce = state.blockchain.get_nonce(FAUCET_ACCOUNT);
+    let tx = Transaction::new(
+        TransactionType::Transfer,
+        FAUCET_ACCOUNT,
+        receiver,
+        amount,
+        Some("Faucet airdrop".to_string()),
+        None,
+        false,
+        None,
+        None,
+        nonce,
+    );
+    match state.blockchain.add_to_mempool(tx) {
+        Ok(hash) => RpcResponse::ok(id, json!({ "txHash": hash, "status": "pending" })),
+        Err(e) => RpcResponse::err(id, -32000, &e),
+    }
+}
+
 fn pecu_get_balance(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     let address = params[0].as_str().unwrap_or("");
     let balance = state.blockchain.get_balance(address);
@@ -513,6 +937,38 @@ fn pecu_get_balance(state: &AppState, id: Option<Value>, params: &Value) -> RpcR
     )
 }
 
+/// Server-side cap on addresses per pecu_getMultipleAccounts call
+pub const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+fn pecu_get_multiple_accounts(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let addresses: Vec<&str> = match params[0].as_array() {
+        Some(list) => list.iter().map(|a| a.as_str().unwrap_or("")).collect(),
+        None => return RpcResponse::err(id, -32602, "Expected an array of addresses"),
+    };
+    if addresses.len() > MAX_ACCOUNTS_PER_REQUEST {
+        return RpcResponse::err(
+            id,
+            -32602,
+            &format!("At most {MAX_ACCOUNTS_PER_REQUEST} addresses per request"),
+        );
+    }
+    let accounts: Vec<Value> = state
+        .blockchain
+        .get_accounts(&addresses)
+        .into_iter()
+        .zip(&addresses)
+        .map(|(account, address)| match account {
+            Some((balance, nonce)) => json!({
+                "address": address,
+                "balance_raw": balance.to_string(),
+                "nonce": nonce
+            }),
+            None => Value::Null,
+        })
+        .collect();
+    RpcResponse::ok(id, json!(accounts))
+}
+
 fn pecu_create_wallet(state: &AppState, id: Option<Value>) -> RpcResponse {
     let wallet = Wallet::new();
     let info = json!({
@@ -530,10 +986,18 @@ fn pecu_create_wallet(state: &AppState, id: Option<Value>) -> RpcResponse {
     RpcResponse::ok(id, info)
 }
 
+/// Accepts either the wallet's EVM (0x) address or its native Base58 address
 fn pecu_get_wallet(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     let address = params[0].as_str().unwrap_or("");
     let wallets = state.wallets.read();
-    match wallets.get(address) {
+    let wallet = if crypto::is_evm_address(address) {
+        wallets.get(address)
+    } else if let Err(e) = crypto::decode_pecu_address(address) {
+        return RpcResponse::err(id, -32602, &e);
+    } else {
+        wallets.values().find(|w| w.keypair.pecu_address == address)
+    };
+    match wallet {
         Some(w) => RpcResponse::ok(
             id,
             json!({
@@ -542,6 +1006,7 @@ fn pecu_get_wallet(state: &AppState, id: Option<Value>, params: &Value) -> RpcRe
                 "pecuAddress": w.keypair.pecu_address,
                 "balance": w.pecu_balance.to_string(),
                 "validatorNodeId": w.validator_node_id,
+                "validatorAuthNonce": w.validator_auth_nonce,
                 "coldStorageKeys": w.cold_storage.len()
             }),
         ),
@@ -549,28 +1014,86 @@ fn pecu_get_wallet(state: &AppState, id: Option<Value>, params: &Value) -> RpcRe
     }
 }
 
+fn validator_to_json(v: &crate::consensus::Validator) -> Value {
+    json!({
+        "nodeId": v.node_id,
+        "walletAddress": v.wallet_address,
+        "stake": v.stake.to_string(),
+        "uptimeSeconds": v.uptime_seconds,
+        "blocksValidated": v.blocks_validated,
+        "isOnline": v.is_online,
+        "isLead": v.is_lead,
+        "lastSeen": v.last_seen,
+        "totalRewardsEarned": v.total_rewards_earned.to_string(),
+        "selectionWeight": v.selection_weight()
+    })
+}
+
 fn pecu_get_validators(state: &AppState, id: Option<Value>) -> RpcResponse {
     let pot = state.pot.read();
-    let validators: Vec<Value> = pot
-        .validators
-        .iter()
-        .map(|v| {
-            json!({
-                "nodeId": v.node_id,
-                "walletAddress": v.wallet_address,
-                "stake": v.stake.to_string(),
-                "uptimeSeconds": v.uptime_seconds,
-                "blocksValidated": v.blocks_validated,
-                "isOnline": v.is_online,
-                "isLead": v.is_lead,
-                "totalRewardsEarned": v.total_rewards_earned.to_string(),
-                "selectionWeight": v.selection_weight()
-            })
-        })
-        .collect();
+    let validators: Vec<Value> = pot.validators.iter().map(validator_to_json).collect();
     RpcResponse::ok(id, json!(validators))
 }
 
+fn pecu_get_validator_set(state: &AppState, id: Option<Value>) -> RpcResponse {
+    let now = chrono::Utc::now().timestamp();
+    let pot = state.pot.read();
+    let (delinquent, current): (Vec<_>, Vec<_>) =
+        pot.validators.iter().partition(|v| v.is_delinquent(now));
+    let active_stake: u128 = current.iter().map(|v| v.stake).sum();
+    RpcResponse::ok(
+        id,
+        json!({
+            "current": current.into_iter().map(validator_to_json).collect::<Vec<_>>(),
+            "delinquent": delinquent.into_iter().map(validator_to_json).collect::<Vec<_>>(),
+            "activeStake": active_stake.to_string(),
+            "totalStake": pot.total_stake().to_string()
+        }),
+    )
+}
+
+/// Lead of the tip block and of the next block. Later leads depend on block
+/// hashes not produced yet (see `ProofOfTime::leader_for_height`), so the
+/// schedule only ever reaches one block ahead.
+fn pecu_get_leader_schedule(state: &AppState, id: Option<Value>) -> RpcResponse {
+    let latest = state.blockchain.latest_block();
+    let next_height = latest.header.height + 1;
+    let next_leader = state
+        .pot
+        .read()
+        .leader_for_height(&latest.hash, next_height);
+    RpcResponse::ok(
+        id,
+        json!({
+            "currentHeight": latest.header.height,
+            "currentLeader": latest.header.validator,
+            "nextHeight": next_height,
+            "nextLeader": next_leader
+        }),
+    )
+}
+
+/// PoT proof of the block at `height`, so light clients can re-run
+/// `crypto::verify_vdf` without downloading the block's transactions
+fn pecu_get_pot_proof(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let height = match params[0].as_u64() {
+        Some(h) => h,
+        None => return RpcResponse::err(id, -32602, "Block height required"),
+    };
+    match state.blockchain.get_block_by_height(height) {
+        Some(block) => RpcResponse::ok(
+            id,
+            json!({
+                "height": block.header.height,
+                "blockHash": block.hash,
+                "validator": block.header.validator,
+                "proof": serde_json::to_value(&block.header.pot_proof).unwrap_or(Value::Null)
+            }),
+        ),
+        None => RpcResponse::ok(id, Value::Null),
+    }
+}
+
 fn pecu_register_validator(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
     use crate::consensus::Validator;
     let address = params[0].as_str().unwrap_or("");
@@ -600,6 +1123,113 @@ fn pecu_register_validator(state: &AppState, id: Option<Value>, params: &Value)
     )
 }
 
+/// Message a validator's wallet signs in DOMAIN_VALIDATOR to authorise a
+/// validator-set change; `nonce` is the wallet's `validator_auth_nonce`, so
+/// a signature cannot be replayed
+pub fn validator_auth_message(action: &str, address: &str, detail: &str, nonce: u64) -> String {
+    format!("{action}:{address}:{detail}:{nonce}")
+}
+
+/// Check the wallet's signature over a validator-set change and consume
+/// its nonce
+fn authorize_validator_change(
+    wallet: &mut Wallet,
+    action: &str,
+    address: &str,
+    detail: &str,
+    signature: &str,
+) -> bool {
+    let message = validator_auth_message(action, address, detail, wallet.validator_auth_nonce);
+    if !wallet
+        .keypair
+        .verify_in_domain(crypto::DOMAIN_VALIDATOR, &message, signature)
+    {
+        return false;
+    }
+    wallet.validator_auth_nonce += 1;
+    true
+}
+
+/// Register or adjust a validator's stake (at most MAX_SUPPLY); must be
+/// signed by the validator's wallet over
+/// `validator_auth_message("setValidatorStake", address, stake, nonce)`
+fn pecu_set_validator_stake(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let address = params[0].as_str().unwrap_or("");
+    let stake = match params[1].as_str().unwrap_or("").parse::<u128>() {
+        Ok(stake) if stake <= Blockchain::MAX_SUPPLY => stake,
+        Ok(_) => return RpcResponse::err(id, -32602, "Stake exceeds the maximum supply"),
+        Err(_) => return RpcResponse::err(id, -32602, "Invalid stake amount"),
+    };
+    let signature = params[2].as_str().unwrap_or("");
+
+    let mut wallets = state.wallets.write();
+    let wallet = match wallets.get_mut(address) {
+        Some(w) => w,
+        None => return RpcResponse::err(id, -32602, "Wallet not found"),
+    };
+    if !authorize_validator_change(
+        wallet,
+        "setValidatorStake",
+        address,
+        &stake.to_string(),
+        signature,
+    ) {
+        return RpcResponse::err(id, -32001, "Unauthorized: invalid signature");
+    }
+
+    let mut pot = state.pot.write();
+    let node_id = pot.set_stake(address, stake);
+    wallet.validator_node_id = Some(node_id.clone());
+
+    RpcResponse::ok(
+        id,
+        json!({
+            "nodeId": node_id,
+            "walletAddress": address,
+            "stake": stake.to_string(),
+            "totalStake": pot.total_stake().to_string(),
+            "status": "updated"
+        }),
+    )
+}
+
+/// Remove a validator from the set; must be signed by the validator's
+/// wallet over `validator_auth_message("deregisterValidator", address, "", nonce)`
+fn pecu_deregister_validator(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    let address = params[0].as_str().unwrap_or("");
+    let signature = params[1].as_str().unwrap_or("");
+
+    let mut wallets = state.wallets.write();
+    let wallet = match wallets.get_mut(address) {
+        Some(w) => w,
+        None => return RpcResponse::err(id, -32602, "Wallet not found"),
+    };
+    let mut pot = state.pot.write();
+    if !pot.validators.iter().any(|v| v.wallet_address == address) {
+        return RpcResponse::err(id, -32602, "Not a registered validator");
+    }
+    if !authorize_validator_change(wallet, "deregisterValidator", address, "", signature) {
+        return RpcResponse::err(id, -32001, "Unauthorized: invalid signature");
+    }
+
+    let removed = match pot.deregister_validator(address) {
+        Some(v) => v,
+        None => return RpcResponse::err(id, -32602, "Not a registered validator"),
+    };
+    wallet.validator_node_id = None;
+
+    RpcResponse::ok(
+        id,
+        json!({
+            "nodeId": removed.node_id,
+            "walletAddress": address,
+            "stake": removed.stake.to_string(),
+            "totalStake": pot.total_stake().to_string(),
+            "status": "deregistered"
+        }),
+    )
+}
+
 fn pecu_get_halving_schedule(_state: &AppState, id: Option<Value>) -> RpcResponse {
     use crate::consensus::HalvingSchedule;
     let schedule = HalvingSchedule::official();
@@ -640,8 +1270,7 @@ fn pecu_get_vesting_schedule(_state: &AppState, id: Option<Value>) -> RpcRespons
 }
 
 fn pecu_mine_block(state: &AppState, id: Option<Value>) -> RpcResponse {
-    use crate::chain::Block;
-
+    state.blockchain.gc_expired_blockhashes();
     let txs = state.blockchain.drain_mempool(1000);
     let latest = state.blockchain.latest_block();
     let seed = format!(
@@ -650,24 +1279,27 @@ fn pecu_mine_block(state: &AppState, id: Option<Value>) -> RpcResponse {
         chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
     );
 
-    let (pot_proof, validator_addr) = state.pot.write().generate_pot_proof(&seed);
+    let pot_proof = state.pot.write().generate_pot_proof(&seed);
 
     let height = state.blockchain.block_height() + 1;
-    let block = Block::new(height, &latest.hash, txs, &validator_addr, pot_proof);
-    let block_hash = block.hash.clone();
-    let tx_count = block.transactions.len();
-
-    match state.blockchain.commit_block(block) {
-        Ok(_) => RpcResponse::ok(
-            id,
-            json!({
-                "blockHash": block_hash,
+    let validator_addr = state.pot.read().leader_for_height(&latest.hash, height);
+    match state
+        .blockchain
+        .produce_block(height, &latest.hash, txs, &validator_addr, pot_proof)
+    {
+        Ok(block) => {
+            state.pot.write().record_block_sealed(&validator_addr);
+            RpcResponse::ok(
+                id,
+                json!({
+                "blockHash": block.hash,
                 "height": height,
-                "txCount": tx_count,
+                "txCount": block.transactions.len(),
                 "validator": validator_addr,
                 "status": "committed"
-            }),
-        ),
+                }),
+            )
+        }
         Err(e) => RpcResponse::err(id, -32000, &e),
     }
 }
@@ -826,6 +1458,56 @@ fn pnp16_transfer(state: &AppState, id: Option<Value>, params: &Value) -> RpcRes
     erc20_transfer(state, id, params)
 }
 
+/// Server-side cap on holders returned per pnp16_getHolders page
+pub const MAX_HOLDERS_PER_PAGE: usize = 1000;
+
+fn pnp16_get_holders(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
+    use crate::tokens::HolderFilter;
+
+    let contract = params[0].as_str().unwrap_or("");
+    let mut filters = Vec::new();
+    for f in params[1].as_array().cloned().unwrap_or_default() {
+        if let Some(min) = f["minBalance"].as_str() {
+            match min.parse::<u128>() {
+                Ok(min) => filters.push(HolderFilter::MinBalance(min)),
+                Err(_) => return RpcResponse::err(id, -32602, "Invalid minBalance filter"),
+            }
+        } else if f["memcmp"].is_object() {
+            let offset = f["memcmp"]["offset"].as_u64().unwrap_or(0) as usize;
+            let bytes = f["memcmp"]["bytes"].as_str().unwrap_or("").to_string();
+            filters.push(HolderFilter::Memcmp { offset, bytes });
+        } else {
+            return RpcResponse::err(id, -32602, "Unknown holder filter");
+        }
+    }
+    let offset = params[2].as_u64().unwrap_or(0) as usize;
+    let limit = (params[3].as_u64().unwrap_or(MAX_HOLDERS_PER_PAGE as u64) as usize)
+        .min(MAX_HOLDERS_PER_PAGE);
+
+    let registry = state.token_registry.read();
+    match registry.get_token(contract) {
+        Some(t) => {
+            let holders = t.holders(&filters);
+            let page: Vec<Value> = holders
+                .iter()
+                .skip(offset)
+                .take(limit)
+                .map(|(addr, bal)| json!({ "address": addr, "balance": bal.to_string() }))
+                .collect();
+            RpcResponse::ok(
+                id,
+                json!({
+                    "holders": page,
+                    "total": holders.len(),
+                    "offset": offset,
+                    "limit": limit
+                }),
+            )
+        }
+        None => RpcResponse::err(id, -32602, "Token not found"),
+    }
+}
+
 // ─── Escrow Methods ───────────────────────────────────────────────────────────
 
 fn escrow_create(state: &AppState, id: Option<Value>, params: &Value) -> RpcResponse {
@@ -14,7 +14,7 @@
 // limitations under the License.
 
 // storage/mod.rs - Persistent storage (sled embedded database)
-use crate::chain::{Block, Transaction};
+use crate::chain::{Block, Blockchain, Transaction};
 use crate::tokens::PNP16Token;
 use crate::wallet::Wallet;
 use serde::{de::DeserializeOwned, Serialize};
@@ -151,6 +151,54 @@ impl ChainStorage {
         String::from_utf8(bytes.to_vec()).ok()
     }
 
+    /// Apply a set of state changes atomically; `None` deletes the key.
+    /// All changes are staged and checked first, then written to sled as one
+    /// `Batch`, which sled applies (and recovers after a crash) as a unit.
+    /// An invalid change (an empty key) fails the whole call before anything
+    /// is written.
+    pub fn apply_state(&self, changes: &[(&str, Option<&str>)]) -> Result<(), sled::Error> {
+        let mut batch = sled::Batch::default();
+        for (key, value) in changes {
+            if key.is_empty() {
+                return Err(sled::Error::Unsupported("empty state key".into()));
+            }
+            match value {
+                Some(v) => batch.insert(key.as_bytes(), v.as_bytes()),
+                None => batch.remove(key.as_bytes()),
+            }
+        }
+        self.state_tree.apply_batch(batch)
+    }
+
+    /// Flush account state (balances, nonces, total burned) in one
+    /// `apply_state` call, so a crash never leaves a mix of two blocks
+    pub fn save_chain_state(&self, blockchain: &Blockchain) -> Result<(), sled::Error> {
+        let entries: Vec<(String, String)> = {
+            // Same lock order as block application
+            let balances = blockchain.balances.read();
+            let burned = blockchain.total_burned.read();
+            let nonces = blockchain.nonces.read();
+            balances
+                .iter()
+                .map(|(address, balance)| (format!("balance:{address}"), balance.to_string()))
+                .chain(
+                    nonces
+                        .iter()
+                        .map(|(address, nonce)| (format!("nonce:{address}"), nonce.to_string())),
+                )
+                .chain(std::iter::once((
+                    "total_burned".to_string(),
+                    burned.to_string(),
+                )))
+                .collect()
+        };
+        let changes: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), Some(value.as_str())))
+            .collect();
+        self.apply_state(&changes)
+    }
+
     pub fn flush(&self) -> Result<(), sled::Error> {
         self.db.flush()?;
         Ok(())
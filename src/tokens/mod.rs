@@ -247,11 +247,49 @@ impl PNP16Token {
         Ok(true)
     }
 
+    /// All non-zero holders matching every filter, ordered by address so that
+    /// offset/limit pagination is stable between calls
+    pub fn holders(&self, filters: &[HolderFilter]) -> Vec<(String, u128)> {
+        let mut result: Vec<(String, u128)> = self
+            .balances
+            .iter()
+            .filter(|(_, &bal)| bal > 0)
+            .filter(|(addr, &bal)| filters.iter().all(|f| f.matches(addr, bal)))
+            .map(|(addr, &bal)| (addr.clone(), bal))
+            .collect();
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        result
+    }
+
     fn record_tx(&mut self, tx: TokenTransaction) {
         self.subset_ledger.push(tx);
     }
 }
 
+// ─── Holder Filters ───────────────────────────────────────────────────────────
+// Used by explorers/dApps to list the accounts held under one token contract
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum HolderFilter {
+    /// Balance must be at least this amount
+    MinBalance(u128),
+    /// Address bytes starting at `offset` must equal `bytes`
+    Memcmp { offset: usize, bytes: String },
+}
+
+impl HolderFilter {
+    pub fn matches(&self, address: &str, balance: u128) -> bool {
+        match self {
+            HolderFilter::MinBalance(min) => balance >= *min,
+            HolderFilter::Memcmp { offset, bytes } => offset
+                .checked_add(bytes.len())
+                .and_then(|end| address.as_bytes().get(*offset..end))
+                .map(|slice| slice == bytes.as_bytes())
+                .unwrap_or(false),
+        }
+    }
+}
+
 // ─── ERC-20 Token (alias / wrapper for full EVM compat) ──────────────────────
 
 pub type ERC20Token = PNP16Token;
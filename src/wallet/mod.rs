@@ -48,15 +48,28 @@ impl KeyPair {
         }
     }
 
+    /// Sign an arbitrary message (DOMAIN_MESSAGE)
     pub fn sign(&self, data: &str) -> String {
-        let combined = format!("{}{}", self.private_key, data);
-        crypto::sha512(combined.as_bytes())
+        self.sign_in_domain(crypto::DOMAIN_MESSAGE, data)
     }
 
     pub fn verify_signature(&self, data: &str, signature: &str) -> bool {
         self.sign(data) == signature
     }
 
+    pub fn sign_in_domain(&self, domain: &str, data: &str) -> String {
+        let combined = format!(
+            "{}{}",
+            self.private_key,
+            crypto::domain_separated(domain, data)
+        );
+        crypto::sha512(combined.as_bytes())
+    }
+
+    pub fn verify_in_domain(&self, domain: &str, data: &str, signature: &str) -> bool {
+        self.sign_in_domain(domain, data) == signature
+    }
+
     /// Refresh public key (Pecu Novus security feature from whitepaper)
     pub fn refresh_public_key(&mut self) {
         let new_pub = crypto::generate_public_key();
@@ -174,6 +187,9 @@ pub struct Wallet {
     pub dak: Option<DevelopmentAccessKey>,
     /// Cold storage: key -> amount locked offline
     pub cold_storage: std::collections::HashMap<String, u128>,
+    /// Next nonce a validator-set change signed by this wallet must carry
+    #[serde(default)]
+    pub validator_auth_nonce: u64,
 }
 
 impl Wallet {
@@ -196,6 +212,7 @@ impl Wallet {
             gak_sessions: Vec::new(),
             dak: None,
             cold_storage: std::collections::HashMap::new(),
+            validator_auth_nonce: 0,
         }
     }
 
@@ -19,13 +19,21 @@
 
 use chrono::Utc;
 use pecu_novus::{
-    chain::{Block, Blockchain, Transaction, TransactionType},
-    consensus::{HalvingSchedule, ProofOfTime, Validator, VestingSchedule},
+    chain::{
+        Block, Blockchain, DrainPolicy, Transaction, TransactionType, TxStatus,
+        LEDGER_ARCHIVE_MAGIC, LEDGER_ARCHIVE_VERSION,
+    },
+    consensus::{BlockPacing, HalvingSchedule, ProofOfTime, Validator, VestingSchedule},
     crypto,
     escrow::{EscrowContract, EscrowStatus, MVault, TransferCard, TransferCardUseCase},
-    tokens::{AssetClass, ERC1400Token, PNP16Token, TokenRegistry},
+    rpc::{
+        apply_limits, dispatch, rpc_router, validator_auth_message, AppState, RpcLimits, RpcRequest,
+    },
+    storage::ChainStorage,
+    tokens::{AssetClass, ERC1400Token, HolderFilter, PNP16Token, TokenRegistry},
     wallet::{DevelopmentAccessKey, GeneralAccessKey, KeyPair, Wallet},
 };
+use serde_json::{json, Value};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // CRYPTO TESTS
@@ -149,6 +157,59 @@ mod crypto_tests {
             .chars()
             .all(|c| "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz".contains(c)));
     }
+
+    #[test]
+    fn test_program_address_deterministic() {
+        let a = crypto::derive_program_address("0xProgram", &[b"vault", b"alice"]);
+        let b = crypto::derive_program_address("0xProgram", &[b"vault", b"alice"]);
+        assert_eq!(a, b);
+        assert!(a.starts_with("0x"));
+        assert_eq!(a.len(), 42);
+    }
+
+    #[test]
+    fn test_program_address_depends_on_seeds_and_program() {
+        let base = crypto::derive_program_address("0xProgram", &[b"vault", b"alice"]);
+        assert_ne!(
+            base,
+            crypto::derive_program_address("0xProgram", &[b"vault", b"bob"])
+        );
+        assert_ne!(
+            base,
+            crypto::derive_program_address("0xOther", &[b"vault", b"alice"])
+        );
+        // Seed boundaries are significant
+        assert_ne!(
+            crypto::derive_program_address("0xProgram", &[b"ab", b"c"]),
+            crypto::derive_program_address("0xProgram", &[b"a", b"bc"])
+        );
+        // Never collides with the keypair-style address of the same input
+        assert_ne!(
+            crypto::derive_program_address("0xProgram", &[]),
+            crypto::public_key_to_address("0xProgram")
+        );
+    }
+
+    #[test]
+    fn test_decode_pecu_address() {
+        let kp = KeyPair::generate();
+        let decoded = crypto::decode_pecu_address(&kp.pecu_address).unwrap();
+        assert_eq!(bs58::encode(decoded).into_string(), kp.pecu_address);
+        assert_eq!(
+            decoded.to_vec(),
+            crypto::sha256_bytes(kp.public_key.as_bytes())
+        );
+
+        // '0', 'O', 'I' and 'l' are not in the Base58 alphabet
+        assert!(crypto::decode_pecu_address("0OIl").is_err());
+        // Valid Base58, but a 20-byte payload
+        let short = bs58::encode([7u8; 20]).into_string();
+        assert!(crypto::decode_pecu_address(&short).is_err());
+
+        assert!(crypto::is_evm_address(&kp.evm_address));
+        assert!(!crypto::is_evm_address(&kp.pecu_address));
+        assert!(!crypto::is_evm_address("0x1234"));
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -246,6 +307,22 @@ mod wallet_tests {
         assert!(!dak.is_valid());
         assert!(dak.revocation_reason.is_some());
     }
+
+    #[test]
+    fn test_signature_bound_to_domain() {
+        let kp = KeyPair::generate();
+        let data = "transfer:0xABC:1000";
+        let tx_sig = kp.sign_in_domain(crypto::DOMAIN_TRANSACTION, data);
+        assert!(kp.verify_in_domain(crypto::DOMAIN_TRANSACTION, data, &tx_sig));
+        assert!(!kp.verify_in_domain(crypto::DOMAIN_MESSAGE, data, &tx_sig));
+        assert!(!kp.verify_signature(data, &tx_sig));
+
+        // Moving bytes between tag and data does not produce the same preimage
+        assert_ne!(
+            crypto::domain_separated("ab", "c"),
+            crypto::domain_separated("a", "bc")
+        );
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -413,14 +490,740 @@ mod chain_tests {
             bc.add_to_mempool(tx).unwrap();
         }
 
-        let txs = bc.drain_mempool(10);
-        let proof = crypto::compute_vdf("test", 5);
-        let block = Block::new(1, &bc.latest_block().hash, txs, "v1", proof);
-        bc.commit_block(block).unwrap();
-
-        assert!(
-            *bc.total_burned.read() > 0,
-            "Burn mechanism must reduce total supply"
+        let txs = bc.drain_mempool(10);
+        let proof = crypto::compute_vdf("test", 5);
+        let block = Block::new(1, &bc.latest_block().hash, txs, "v1", proof);
+        bc.commit_block(block).unwrap();
+
+        assert!(
+            *bc.total_burned.read() > 0,
+            "Burn mechanism must reduce total supply"
+        );
+    }
+
+    #[test]
+    fn test_commit_block_rolls_back_on_failed_tx() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 1_000_000u128);
+        bc.balances
+            .write()
+            .insert("carol".to_string(), 1_000_000u128);
+
+        // Each transfer passes mempool validation on its own, but together
+        // they overdraw alice: only the second one is rolled back.
+        let first = make_test_tx("alice", "bob", 600_000);
+        let mut second = make_test_tx("alice", "erin", 600_000);
+        second.nonce = 1;
+        second.tx_hash = second.compute_hash();
+        let unrelated = make_test_tx("carol", "dave", 1_000);
+        let (second_hash, unrelated_hash) = (second.tx_hash.clone(), unrelated.tx_hash.clone());
+        bc.add_to_mempool(first).unwrap();
+        bc.add_to_mempool(second).unwrap();
+        bc.add_to_mempool(unrelated).unwrap();
+
+        let txs = bc.drain_mempool(10);
+        let proof = crypto::compute_vdf("rollback", 5);
+        let latest = bc.latest_block();
+        let block = bc
+            .produce_block(1, &latest.hash, txs, "validator1", proof)
+            .unwrap();
+
+        assert_eq!(block.transactions.len(), 2);
+        assert_eq!(block.header.tx_count, 2);
+        assert!(bc.verify_chain());
+        assert_eq!(bc.block_height(), 1);
+        assert_eq!(bc.get_balance("bob"), 600_000);
+        assert_eq!(bc.get_balance("erin"), 0);
+        assert_eq!(bc.get_balance("dave"), 1_000);
+        assert_eq!(bc.get_nonce("alice"), 1);
+        assert_eq!(
+            bc.transaction_status(&unrelated_hash),
+            Some(TxStatus::Confirmed { block_height: 1 })
+        );
+        match bc.transaction_status(&second_hash) {
+            Some(TxStatus::Dropped { reason }) => assert!(reason.contains("Insufficient")),
+            other => panic!("expected dropped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_commit_block_rejects_burn_beyond_balance() {
+        let bc = Blockchain::new();
+        bc.balances.write().insert("alice".to_string(), 500u128);
+        let tx = Transaction::new(
+            TransactionType::Burn,
+            "alice",
+            "0x0000000000000000000000000000000000000000",
+            1_000,
+            None,
+            None,
+            false,
+            None,
+            None,
+            0,
+        );
+        let proof = crypto::compute_vdf("burn", 5);
+        let block = Block::new(1, &bc.latest_block().hash, vec![tx], "v1", proof);
+        assert!(bc.commit_block(block).is_err());
+        assert_eq!(bc.get_balance("alice"), 500);
+        assert_eq!(*bc.total_burned.read(), 0);
+    }
+
+    #[test]
+    fn test_mempool_snapshot_reflects_pending_txs() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        for i in 0..3 {
+            let mut tx = make_test_tx("alice", "bob", 1_000 + i as u128);
+            tx.nonce = i;
+            tx.tx_hash = tx.compute_hash();
+            bc.add_to_mempool(tx).unwrap();
+        }
+
+        let snapshot = bc.mempool_snapshot(10);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot[0].sender, "alice");
+        assert_eq!(snapshot[2].nonce, 2);
+        assert_eq!(snapshot[2].position, 2);
+        assert_eq!(snapshot[0].tx_hash, bc.mempool.read()[0].tx_hash);
+
+        assert_eq!(bc.mempool_snapshot(2).len(), 2, "Snapshot respects limit");
+    }
+
+    #[test]
+    fn test_genesis_is_deterministic() {
+        assert_eq!(Block::genesis().hash, Block::genesis().hash);
+        assert_eq!(Block::genesis().header.timestamp, 1484438400);
+    }
+
+    fn mine_foundation_blocks(bc: &Blockchain, count: u64) {
+        for i in 0..count {
+            let tx = Transaction::new(
+                TransactionType::Transfer,
+                "PecuNovusFoundation",
+                "bob",
+                1_000_000 + i as u128,
+                None,
+                None,
+                false,
+                None,
+                None,
+                i,
+            );
+            let proof = crypto::compute_vdf(&format!("archive_{i}"), 5);
+            let block = Block::new(
+                bc.block_height() + 1,
+                &bc.latest_block().hash,
+                vec![tx],
+                "validator1",
+                proof,
+            );
+            bc.commit_block(block).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ledger_export_import_round_trip() {
+        let source = Blockchain::new();
+        mine_foundation_blocks(&source, 3);
+
+        let mut archive = Vec::new();
+        assert_eq!(source.export_range(0, 3, &mut archive).unwrap(), 4);
+
+        let target = Blockchain::new();
+        assert_eq!(target.import_range(archive.as_slice()).unwrap(), 3);
+        assert_eq!(target.block_height(), 3);
+        for h in 0..=3 {
+            assert_eq!(
+                target.get_block_by_height(h).unwrap().hash,
+                source.get_block_by_height(h).unwrap().hash
+            );
+        }
+        assert_eq!(target.get_balance("bob"), source.get_balance("bob"));
+    }
+
+    #[test]
+    fn test_ledger_import_rejects_gap_and_tampering() {
+        let source = Blockchain::new();
+        mine_foundation_blocks(&source, 3);
+
+        // Range starting past the target's tip cannot be linked
+        let mut archive = Vec::new();
+        source.export_range(2, 3, &mut archive).unwrap();
+        assert!(Blockchain::new().import_range(archive.as_slice()).is_err());
+
+        // Bad magic
+        assert!(Blockchain::new().import_range(&b"NOTALDGR"[..]).is_err());
+
+        // Tampered block body no longer matches its header hash
+        let mut archive = Vec::new();
+        source.export_range(1, 1, &mut archive).unwrap();
+        let text = String::from_utf8(archive).unwrap();
+        let tampered = text.replacen("validator1", "validatorX", 1);
+        assert!(Blockchain::new().import_range(tampered.as_bytes()).is_err());
+
+        // Tampered transaction under an untouched header
+        let body_only = text.replacen("\"receiver\":\"bob\"", "\"receiver\":\"eve\"", 1);
+        assert_ne!(body_only, text);
+        let target = Blockchain::new();
+        let err = target.import_range(body_only.as_bytes()).unwrap_err();
+        assert!(err.contains("do not match"));
+        assert_eq!(target.get_balance("eve"), 0);
+
+        // A length prefix beyond any valid block is refused before reading
+        let mut oversized = Vec::new();
+        oversized.extend_from_slice(LEDGER_ARCHIVE_MAGIC);
+        oversized.extend_from_slice(&LEDGER_ARCHIVE_VERSION.to_be_bytes());
+        oversized.extend_from_slice(&1u64.to_be_bytes());
+        oversized.extend_from_slice(&u32::MAX.to_be_bytes());
+        let err = Blockchain::new()
+            .import_range(oversized.as_slice())
+            .unwrap_err();
+        assert!(err.contains("too large"));
+
+        assert!(source.export_range(2, 9, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_recent_blockhash_accepted_stale_rejected() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        let old_hash = bc.latest_block().hash;
+
+        let fresh = make_test_tx("alice", "bob", 100).with_recent_blockhash(&old_hash);
+        assert!(bc.validate_transaction(&fresh).is_ok());
+
+        // Push the old hash out of the validity window with empty blocks
+        for i in 0..Blockchain::RECENT_BLOCKHASH_WINDOW {
+            let proof = crypto::compute_vdf(&format!("window_{i}"), 1);
+            let block = Block::new(
+                bc.block_height() + 1,
+                &bc.latest_block().hash,
+                vec![],
+                "v1",
+                proof,
+            );
+            bc.commit_block(block).unwrap();
+        }
+        assert!(!bc.is_recent_blockhash(&old_hash));
+
+        let stale = make_test_tx("alice", "bob", 100).with_recent_blockhash(&old_hash);
+        assert!(bc.add_to_mempool(stale).is_err());
+
+        let unknown = make_test_tx("alice", "bob", 100).with_recent_blockhash("deadbeef");
+        assert!(bc.add_to_mempool(unknown).is_err());
+
+        let current = bc.latest_block().hash;
+        let ok = make_test_tx("alice", "bob", 100).with_recent_blockhash(&current);
+        assert!(bc.add_to_mempool(ok).is_ok());
+    }
+
+    #[test]
+    fn test_gc_expired_blockhashes_drops_aged_out_txs() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        let old_hash = bc.latest_block().hash;
+
+        let pinned = make_test_tx("alice", "bob", 100).with_recent_blockhash(&old_hash);
+        let pinned_hash = bc.add_to_mempool(pinned).unwrap();
+        let legacy = make_test_tx("alice", "carol", 100);
+        let legacy_hash = bc.add_to_mempool(legacy).unwrap();
+        assert_eq!(bc.gc_expired_blockhashes(), 0);
+
+        for i in 0..Blockchain::RECENT_BLOCKHASH_WINDOW {
+            let proof = crypto::compute_vdf(&format!("gc_{i}"), 1);
+            let block = Block::new(
+                bc.block_height() + 1,
+                &bc.latest_block().hash,
+                vec![],
+                "v1",
+                proof,
+            );
+            bc.commit_block(block).unwrap();
+        }
+
+        assert_eq!(bc.gc_expired_blockhashes(), 1);
+        let pending: Vec<String> = bc
+            .mempool_snapshot(10)
+            .into_iter()
+            .map(|e| e.tx_hash)
+            .collect();
+        assert_eq!(pending, vec![legacy_hash]);
+        assert!(!pending.contains(&pinned_hash));
+        assert_eq!(
+            bc.transaction_status(&pinned_hash),
+            Some(TxStatus::Dropped {
+                reason: format!("Blockhash expired: {old_hash}")
+            })
+        );
+    }
+
+    #[test]
+    fn test_transaction_status_pending_then_confirmed() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 100_000_000u128);
+
+        let hash = bc
+            .add_to_mempool(make_test_tx("alice", "bob", 10_000))
+            .unwrap();
+        assert_eq!(bc.transaction_status(&hash), Some(TxStatus::Pending));
+
+        let txs = bc.drain_mempool(10);
+        let proof = crypto::compute_vdf("status_seed", 5);
+        let block = Block::new(1, &bc.latest_block().hash, txs, "v1", proof);
+        bc.commit_block(block).unwrap();
+
+        assert_eq!(
+            bc.transaction_status(&hash),
+            Some(TxStatus::Confirmed { block_height: 1 })
+        );
+        assert_eq!(bc.transaction_status("0xunknown"), None);
+    }
+
+    #[test]
+    fn test_verify_chain_links() {
+        let bc = Blockchain::new();
+        for i in 0..3 {
+            let proof = crypto::compute_vdf(&format!("link_{i}"), 1);
+            let block = Block::new(
+                bc.block_height() + 1,
+                &bc.latest_block().hash,
+                vec![],
+                "v1",
+                proof,
+            );
+            bc.commit_block(block).unwrap();
+        }
+        assert!(bc.verify_chain());
+
+        let blocks = bc.chain.read().clone();
+        assert!(Block::verify_chain(&blocks));
+
+        // Dropping a block breaks the link between its neighbours
+        let mut gapped = blocks.clone();
+        gapped.remove(2);
+        assert!(!Block::verify_chain(&gapped));
+
+        // Rewriting a parent pointer breaks the header hash
+        let mut tampered = blocks;
+        tampered[3].header.previous_hash = "0xforged".to_string();
+        assert!(!Block::verify_chain(&tampered));
+    }
+
+    #[test]
+    fn test_pending_per_sender_cap() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("spammer".to_string(), 999_999_999_999u128);
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+
+        for i in 0..Blockchain::MAX_PENDING_PER_SENDER {
+            let tx = make_test_tx("spammer", &format!("sink{i}"), 1);
+            bc.add_to_mempool(tx).unwrap();
+        }
+        let err = bc
+            .add_to_mempool(make_test_tx("spammer", "one_more", 1))
+            .unwrap_err();
+        assert!(err.contains("Too many pending"));
+
+        // Other senders are unaffected
+        assert!(bc.add_to_mempool(make_test_tx("alice", "bob", 100)).is_ok());
+
+        // Draining the pool frees the sender's slots again
+        bc.drain_mempool(usize::MAX);
+        assert!(bc
+            .add_to_mempool(make_test_tx("spammer", "after_drain", 1))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_replay_reproduces_state_root() {
+        let bc = Blockchain::new();
+        mine_foundation_blocks(&bc, 4);
+        let blocks = bc.chain.read().clone();
+
+        let replayed = Blockchain::replay(&blocks).unwrap();
+        assert_eq!(replayed.state_root(), bc.state_root());
+        assert_eq!(replayed.get_balance("bob"), bc.get_balance("bob"));
+
+        // Replaying again is idempotent
+        let again = Blockchain::replay(&blocks).unwrap();
+        assert_eq!(again.state_root(), replayed.state_root());
+
+        // State that did not come from blocks changes the root
+        bc.balances.write().insert("carol".to_string(), 1);
+        assert_ne!(replayed.state_root(), bc.state_root());
+
+        // A doctored block is refused
+        let mut forged = blocks.clone();
+        forged[2].transactions[0].amount += 1;
+        forged[2].header.merkle_root = "0xforged".to_string();
+        assert!(Blockchain::replay(&forged).is_err());
+        assert!(Blockchain::replay(&[]).is_err());
+
+        // So is a doctored transaction body under an untouched header
+        let mut body_only = blocks;
+        body_only[2].transactions[0].receiver = "mallory".to_string();
+        body_only[2].transactions[0].amount = 1_000_000_000;
+        assert!(Blockchain::replay(&body_only).is_err());
+    }
+
+    #[test]
+    fn test_priority_senders_drained_first_within_reserve() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        bc.balances
+            .write()
+            .insert("governance".to_string(), 999_999_999u128);
+        bc.priority_senders.write().insert("governance".to_string());
+
+        for i in 0..6 {
+            bc.add_to_mempool(make_test_tx("alice", &format!("a{i}"), 100))
+                .unwrap();
+        }
+        for i in 0..3 {
+            bc.add_to_mempool(make_test_tx("governance", &format!("g{i}"), 100))
+                .unwrap();
+        }
+
+        // 25% of a batch of 4 is one reserved slot
+        let batch = bc.drain_mempool(4);
+        let senders: Vec<&str> = batch.iter().map(|t| t.sender.as_str()).collect();
+        assert_eq!(senders, vec!["governance", "alice", "alice", "alice"]);
+        assert_eq!(batch[1].receiver, "a0");
+
+        // Everything not drained stays queued in arrival order
+        let left: Vec<String> = bc
+            .drain_mempool(100)
+            .into_iter()
+            .map(|t| t.receiver)
+            .collect();
+        assert_eq!(left, vec!["g1", "g2", "a3", "a4", "a5"]);
+    }
+
+    #[test]
+    fn test_tx_index_tracks_committed_heights() {
+        let bc = Blockchain::new();
+        assert_eq!(bc.transaction_height(&crypto::sha256(b"genesis")), Some(0));
+
+        mine_foundation_blocks(&bc, 3);
+        for height in 1..=3u64 {
+            let block = bc.get_block_by_height(height).unwrap();
+            let hash = &block.transactions[0].tx_hash;
+            assert_eq!(bc.transaction_height(hash), Some(height));
+            assert_eq!(bc.get_transaction(hash).unwrap().tx_hash, *hash);
+        }
+        assert_eq!(bc.tx_index.read().len(), 4);
+        assert_eq!(bc.transaction_height("0xmissing"), None);
+        assert!(bc.get_transaction("0xmissing").is_none());
+    }
+
+    #[test]
+    fn test_round_robin_drain_prevents_flooding() {
+        let bc = Blockchain::new();
+        bc.balances
+            .write()
+            .insert("flooder".to_string(), 999_999_999u128);
+        bc.balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        for i in 0..10 {
+            bc.add_to_mempool(make_test_tx("flooder", &format!("f{i}"), 100))
+                .unwrap();
+        }
+        bc.add_to_mempool(make_test_tx("alice", "bob", 100))
+            .unwrap();
+
+        // In arrival order alice would wait behind all ten flood txs
+        *bc.drain_policy.write() = DrainPolicy::RoundRobin;
+        let batch: Vec<String> = bc
+            .drain_mempool(3)
+            .into_iter()
+            .map(|t| t.receiver)
+            .collect();
+        assert_eq!(batch, vec!["f0", "bob", "f1"]);
+
+        // The flooder's remaining txs stay queued in order
+        let rest: Vec<String> = bc
+            .drain_mempool(100)
+            .into_iter()
+            .map(|t| t.receiver)
+            .collect();
+        assert_eq!(rest, (2..10).map(|i| format!("f{i}")).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_header_hash_commits_to_every_field() {
+        let proof = crypto::compute_vdf("header_seed", 5);
+        let block = Block::new_at(7, "0xparent", vec![], "v1", proof, 1_700_000_000);
+        let base = block.header.clone();
+        assert_eq!(base.compute_hash(), block.hash);
+        assert_eq!(base.clone().compute_hash(), base.compute_hash());
+
+        let mut variants = Vec::new();
+        let mut h = base.clone();
+        h.height += 1;
+        variants.push(h);
+        let mut h = base.clone();
+        h.previous_hash.push('0');
+        variants.push(h);
+        let mut h = base.clone();
+        h.merkle_root.push('0');
+        variants.push(h);
+        let mut h = base.clone();
+        h.timestamp += 1;
+        variants.push(h);
+        let mut h = base.clone();
+        h.validator = "v2".to_string();
+        variants.push(h);
+        let mut h = base.clone();
+        h.pot_proof.delay += 1;
+        variants.push(h);
+        let mut h = base.clone();
+        h.state_hash.push('0');
+        variants.push(h);
+        let mut h = base.clone();
+        h.version += 1;
+        variants.push(h);
+        let mut h = base.clone();
+        h.tx_count += 1;
+        variants.push(h);
+
+        for changed in &variants {
+            assert_ne!(changed.compute_hash(), block.hash);
+        }
+
+        // Without field boundaries "7" ++ "0xparent" == "70" ++ "xparent"
+        let mut shifted = base.clone();
+        shifted.height = 70;
+        shifted.previous_hash = "xparent".to_string();
+        assert_ne!(shifted.compute_hash(), block.hash);
+    }
+
+    #[test]
+    fn test_drain_respects_block_byte_cap() {
+        let bc = Blockchain::new();
+        for i in 0..5 {
+            let mut tx = make_test_tx("PecuNovusFoundation", &format!("0xbytes{i}"), 1);
+            tx.nonce = i;
+            tx.note = Some("x".repeat(1000));
+            tx.tx_hash = tx.compute_hash();
+            bc.add_to_mempool(tx).unwrap();
+        }
+        let size = bc.mempool.read()[0].encoded_len();
+
+        // Room for two txs by size, even though the count cap allows all five
+        let batch = bc.drain_mempool_within(10, size * 2 + size / 2);
+        assert_eq!(batch.len(), 2);
+        assert_eq!(bc.mempool.read().len(), 3);
+        assert_eq!(bc.mempool.read()[0].nonce, 2);
+    }
+
+    #[test]
+    fn test_oversized_block_rejected() {
+        let bc = Blockchain::new();
+        let mut tx = make_test_tx("PecuNovusFoundation", "0xbig", 1);
+        tx.note = Some("x".repeat(Blockchain::MAX_BLOCK_BYTES));
+        tx.tx_hash = tx.compute_hash();
+        assert!(bc.add_to_mempool(tx.clone()).is_err());
+
+        let proof = crypto::compute_vdf("big", 5);
+        let block = Block::new(1, &bc.latest_block().hash, vec![tx], "v1", proof);
+        let err = bc.commit_block(block).unwrap_err();
+        assert!(err.contains("bytes"));
+        assert_eq!(bc.block_height(), 0);
+    }
+
+    #[test]
+    fn test_drain_skips_tx_over_byte_cap_at_head() {
+        let bc = Blockchain::new();
+        let mut big = make_test_tx("PecuNovusFoundation", "0xbig", 1);
+        big.note = Some("x".repeat(4096));
+        big.tx_hash = big.compute_hash();
+        let big_hash = big.tx_hash.clone();
+        bc.add_to_mempool(big).unwrap();
+        for i in 1..4 {
+            let mut tx = make_test_tx("PecuNovusFoundation", &format!("0xsmall{i}"), 1);
+            tx.nonce = i;
+            tx.tx_hash = tx.compute_hash();
+            bc.add_to_mempool(tx).unwrap();
+        }
+
+        // The oversized head does not stall the queue behind it
+        let batch = bc.drain_mempool_within(10, 2048);
+        assert_eq!(batch.len(), 3);
+        assert!(bc.mempool.read().is_empty());
+        match bc.transaction_status(&big_hash) {
+            Some(TxStatus::Dropped { reason }) => assert!(reason.contains("Too large")),
+            other => panic!("expected dropped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_chain_binds_transaction_bodies() {
+        let bc = Blockchain::new();
+        mine_foundation_blocks(&bc, 3);
+        let blocks = bc.chain.read().clone();
+        assert!(Block::verify_chain(&blocks));
+
+        let mut amount = blocks.clone();
+        amount[2].transactions[0].amount += 1;
+        assert!(!Block::verify_chain(&amount));
+
+        let mut receiver = blocks.clone();
+        receiver[2].transactions[0].receiver = "mallory".to_string();
+        assert!(!Block::verify_chain(&receiver));
+
+        let mut tx_type = blocks.clone();
+        tx_type[2].transactions[0].tx_type = TransactionType::ValidatorReward;
+        assert!(!Block::verify_chain(&tx_type));
+
+        // Re-deriving the tx hash still breaks the header's merkle root
+        let mut rehashed = blocks.clone();
+        let tx = &mut rehashed[2].transactions[0];
+        tx.receiver = "mallory".to_string();
+        tx.tx_hash = tx.compute_hash();
+        assert!(!Block::verify_chain(&rehashed));
+
+        let mut genesis = blocks;
+        genesis[0].transactions[0].amount += 1;
+        assert!(!Block::verify_chain(&genesis));
+    }
+
+    #[test]
+    fn test_set_priority_senders_from_list() {
+        let bc = Blockchain::new();
+        assert_eq!(bc.set_priority_senders(" governance, treasury ,,"), 2);
+        assert!(bc.priority_senders.read().contains("governance"));
+        assert!(bc.priority_senders.read().contains("treasury"));
+
+        // A new list replaces the old one
+        assert_eq!(bc.set_priority_senders(""), 0);
+        assert!(bc.priority_senders.read().is_empty());
+    }
+
+    #[test]
+    fn test_drain_policy_parses_env_values() {
+        assert_eq!("fifo".parse::<DrainPolicy>(), Ok(DrainPolicy::Fifo));
+        assert_eq!(
+            " Round-Robin ".parse::<DrainPolicy>(),
+            Ok(DrainPolicy::RoundRobin)
+        );
+        assert!("lifo".parse::<DrainPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_mempool_rejects_tx_hash_not_matching_contents() {
+        let bc = Blockchain::new();
+        let victim = make_test_tx("PecuNovusFoundation", "bob", 1_000);
+
+        // Edited after hashing
+        let mut stale = make_test_tx("PecuNovusFoundation", "eve", 1_000);
+        stale.amount = 5;
+        assert!(bc.add_to_mempool(stale).is_err());
+
+        // Reusing another transaction's hash
+        let mut forged = make_test_tx("PecuNovusFoundation", "eve", 5);
+        forged.tx_hash = victim.tx_hash.clone();
+        let err = bc.add_to_mempool(forged).unwrap_err();
+        assert!(err.contains("does not match"));
+        assert!(bc.mempool.read().is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_tx_hash_rejected_and_index_not_repointed() {
+        let bc = Blockchain::new();
+        let tx = make_test_tx("PecuNovusFoundation", "bob", 1_000);
+        bc.add_to_mempool(tx.clone()).unwrap();
+        assert!(bc
+            .add_to_mempool(tx.clone())
+            .unwrap_err()
+            .contains("already pending"));
+
+        let latest = bc.latest_block();
+        let proof = crypto::compute_vdf("dup1", 5);
+        bc.produce_block(1, &latest.hash, bc.drain_mempool(10), "v1", proof)
+            .unwrap();
+        assert!(bc
+            .add_to_mempool(tx.clone())
+            .unwrap_err()
+            .contains("already committed"));
+
+        // A later block repeating the hash does not move the index
+        let proof = crypto::compute_vdf("dup2", 5);
+        let block = Block::new(2, &bc.latest_block().hash, vec![tx.clone()], "v1", proof);
+        bc.commit_block(block).unwrap();
+        assert_eq!(bc.transaction_height(&tx.tx_hash), Some(1));
+        assert_eq!(bc.get_transaction(&tx.tx_hash).unwrap().receiver, "bob");
+    }
+
+    #[test]
+    fn test_produce_block_rejects_stale_tip_and_requeues() {
+        let bc = Blockchain::new();
+        bc.balances.write().insert("alice".to_string(), 1_000_000);
+        let genesis = bc.latest_block();
+
+        // Two producers read the same tip; the second must not commit
+        let first = bc
+            .produce_block(1, &genesis.hash, vec![], "v1", crypto::compute_vdf("a", 5))
+            .unwrap();
+        let tx = make_test_tx("alice", "bob", 1_000);
+        let err = bc
+            .produce_block(
+                1,
+                &genesis.hash,
+                vec![tx.clone()],
+                "v2",
+                crypto::compute_vdf("b", 5),
+            )
+            .unwrap_err();
+        assert!(err.contains("does not extend local tip"));
+        assert_eq!(bc.block_height(), 1);
+        assert_eq!(bc.latest_block().hash, first.hash);
+        assert_eq!(bc.get_balance("bob"), 0);
+        assert_eq!(bc.mempool.read()[0].tx_hash, tx.tx_hash);
+        assert!(bc.verify_chain());
+
+        // commit_block applies the same check
+        let stale = Block::new(1, &genesis.hash, vec![], "v3", crypto::compute_vdf("c", 5));
+        assert!(bc.commit_block(stale).is_err());
+        assert_eq!(bc.block_height(), 1);
+    }
+
+    #[test]
+    fn test_dropped_reasons_are_bounded() {
+        let bc = Blockchain::new();
+        {
+            let mut dropped = bc.dropped.write();
+            for i in 0..=Blockchain::MAX_DROPPED_TXS {
+                dropped.insert(format!("0xdrop{i}"), "Blockhash expired".to_string());
+            }
+            assert_eq!(dropped.len(), Blockchain::MAX_DROPPED_TXS);
+        }
+
+        // The oldest drop is forgotten and reads as unknown
+        assert_eq!(bc.transaction_status("0xdrop0"), None);
+        let newest = format!("0xdrop{}", Blockchain::MAX_DROPPED_TXS);
+        assert_eq!(
+            bc.transaction_status(&newest),
+            Some(TxStatus::Dropped {
+                reason: "Blockhash expired".to_string()
+            })
         );
     }
 }
@@ -456,9 +1259,9 @@ mod consensus_tests {
     fn test_pot_generates_valid_proof() {
         let mut pot = ProofOfTime::new();
         pot.register_validator(Validator::new("0xAlice", 1_000));
-        let (proof, validator) = pot.generate_pot_proof("block_hash_seed");
+        let proof = pot.generate_pot_proof("block_hash_seed");
         assert!(!proof.output.is_empty());
-        assert_eq!(validator, "0xAlice");
+        assert_eq!(pot.leader_for_height("parent", 1), "0xAlice");
         assert!(pot.verify_proof(&proof));
     }
 
@@ -531,6 +1334,97 @@ mod consensus_tests {
         pot.register_validator(v);
         assert_eq!(pot.online_validators().len(), 0);
     }
+
+    #[test]
+    fn test_set_stake_updates_and_registers() {
+        let mut pot = ProofOfTime::new();
+        pot.register_validator(Validator::new("0xAlice", 1_000));
+        let alice_id = pot.validators[0].node_id.clone();
+
+        assert_eq!(pot.set_stake("0xAlice", 4_000), alice_id);
+        pot.set_stake("0xBob", 500);
+
+        assert_eq!(pot.validators.len(), 2, "Still one validator per wallet");
+        assert_eq!(pot.validators[0].stake, 4_000);
+        assert_eq!(pot.total_stake(), 4_500);
+    }
+
+    #[test]
+    fn test_leader_for_height_resists_grinding() {
+        let mut pot = ProofOfTime::new();
+        for name in ["0xAlice", "0xBob", "0xCarol", "0xDave"] {
+            let mut v = Validator::new(name, 1_000);
+            v.uptime_seconds = 86400;
+            pot.register_validator(v);
+        }
+        let parent = crypto::sha256(b"parent_block");
+        let leader = pot.leader_for_height(&parent, 42);
+
+        // The proposer varying its own PoT seed does not move the lead
+        for i in 0..10 {
+            pot.generate_pot_proof(&format!("{parent}_{i}"));
+            assert_eq!(pot.leader_for_height(&parent, 42), leader);
+        }
+
+        // Different chain history rotates the lead across validators
+        let leaders: std::collections::HashSet<String> = (0..64)
+            .map(|h| pot.leader_for_height(&crypto::sha256(format!("b{h}").as_bytes()), h))
+            .collect();
+        assert!(leaders.len() > 1);
+    }
+
+    #[test]
+    fn test_block_pacing_deadline_and_full_block() {
+        use std::time::Duration;
+        let pacing = BlockPacing {
+            block_time: Duration::from_millis(2000),
+            max_block_txs: 100,
+            max_block_bytes: 1024 * 1024,
+        };
+
+        // A few txs wait for the deadline
+        assert!(!pacing.should_seal(5, Duration::from_millis(500)));
+        assert!(pacing.should_seal(5, Duration::from_millis(2000)));
+
+        // A full block is sealed early
+        assert!(pacing.should_seal(100, Duration::from_millis(10)));
+
+        // Nothing pending never seals
+        assert!(!pacing.should_seal(0, Duration::from_secs(60)));
+        assert_eq!(pacing.poll_interval(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_deregister_validator_changes_leaders() {
+        let mut pot = ProofOfTime::new();
+        pot.set_stake("0xstay", 1_000);
+        pot.set_stake("0xleave", 1_000_000);
+
+        let removed = pot.deregister_validator("0xleave").unwrap();
+        assert_eq!(removed.stake, 1_000_000);
+        assert_eq!(pot.total_stake(), 1_000);
+        assert!(pot.deregister_validator("0xleave").is_none());
+
+        // Every later height is led by the remaining validator
+        for h in 1..20 {
+            let prev = crypto::sha256(format!("b{h}").as_bytes());
+            assert_eq!(pot.leader_for_height(&prev, h), "0xstay");
+        }
+    }
+
+    #[test]
+    fn test_record_block_sealed_refreshes_last_seen() {
+        let mut pot = ProofOfTime::new();
+        let mut v = Validator::new("0xLead", 1_000);
+        v.last_seen -= 120;
+        pot.register_validator(v);
+
+        assert!(pot.record_block_sealed("0xLead"));
+        let lead = &pot.validators[0];
+        assert!(!lead.is_delinquent(Utc::now().timestamp()));
+        assert!(lead.uptime_seconds >= 120);
+        assert!(!pot.record_block_sealed("0xUnknown"));
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -740,6 +1634,60 @@ mod token_tests {
         assert_eq!(gold.asset_class, AssetClass::PhysicalCommodity);
         assert_eq!(realty.asset_class, AssetClass::FractionalRealEstate);
     }
+
+    fn make_holder_token() -> PNP16Token {
+        let mut t = make_token("HolderToken", "HLD", 1_000);
+        t.transfer("0xCreator", "0xaa01", 100).unwrap();
+        t.transfer("0xCreator", "0xaa02", 5).unwrap();
+        t.transfer("0xCreator", "0xbb01", 300).unwrap();
+        t
+    }
+
+    #[test]
+    fn test_holders_unfiltered_sorted_by_address() {
+        let t = make_holder_token();
+        let holders = t.holders(&[]);
+        let addrs: Vec<&str> = holders.iter().map(|(a, _)| a.as_str()).collect();
+        assert_eq!(addrs, vec!["0xCreator", "0xaa01", "0xaa02", "0xbb01"]);
+    }
+
+    #[test]
+    fn test_holders_min_balance_filter() {
+        let t = make_holder_token();
+        let holders = t.holders(&[HolderFilter::MinBalance(100)]);
+        let addrs: Vec<&str> = holders.iter().map(|(a, _)| a.as_str()).collect();
+        assert_eq!(addrs, vec!["0xCreator", "0xaa01", "0xbb01"]);
+    }
+
+    #[test]
+    fn test_holders_memcmp_filter_combined() {
+        let t = make_holder_token();
+        let holders = t.holders(&[
+            HolderFilter::Memcmp {
+                offset: 2,
+                bytes: "aa".to_string(),
+            },
+            HolderFilter::MinBalance(50),
+        ]);
+        assert_eq!(holders, vec![("0xaa01".to_string(), 100)]);
+    }
+
+    #[test]
+    fn test_holders_memcmp_out_of_range_never_matches() {
+        let t = make_holder_token();
+        let holders = t.holders(&[HolderFilter::Memcmp {
+            offset: 40,
+            bytes: "aa".to_string(),
+        }]);
+        assert!(holders.is_empty());
+
+        // An offset near usize::MAX must not overflow
+        let holders = t.holders(&[HolderFilter::Memcmp {
+            offset: usize::MAX,
+            bytes: "aa".to_string(),
+        }]);
+        assert!(holders.is_empty());
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1070,6 +2018,790 @@ mod tokenomics_tests {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// STORAGE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod storage_tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_state_writes_and_deletes_together() {
+        let store = ChainStorage::in_memory().unwrap();
+        store.set_state("alice", "100").unwrap();
+        store.set_state("bob", "50").unwrap();
+
+        store
+            .apply_state(&[("alice", Some("70")), ("bob", None), ("carol", Some("30"))])
+            .unwrap();
+
+        assert_eq!(store.get_state("alice").as_deref(), Some("70"));
+        assert_eq!(store.get_state("bob"), None);
+        assert_eq!(store.get_state("carol").as_deref(), Some("30"));
+    }
+
+    #[test]
+    fn test_apply_state_failure_partway_writes_nothing() {
+        let store = ChainStorage::in_memory().unwrap();
+        store.set_state("alice", "100").unwrap();
+
+        // The invalid change comes after valid ones; none of them may land
+        let result = store.apply_state(&[
+            ("alice", Some("70")),
+            ("carol", Some("30")),
+            ("", Some("1")),
+            ("dave", None),
+        ]);
+        assert!(result.is_err());
+        assert_eq!(store.get_state("alice").as_deref(), Some("100"));
+        assert_eq!(store.get_state("carol"), None);
+    }
+
+    #[test]
+    fn test_save_chain_state_flushes_accounts() {
+        let store = ChainStorage::in_memory().unwrap();
+        let bc = Blockchain::new();
+        bc.balances.write().insert("alice".to_string(), 1_000);
+        bc.nonces.write().insert("alice".to_string(), 3);
+        *bc.total_burned.write() = 42;
+
+        store.save_chain_state(&bc).unwrap();
+        assert_eq!(store.get_state("balance:alice").as_deref(), Some("1000"));
+        assert_eq!(store.get_state("nonce:alice").as_deref(), Some("3"));
+        assert_eq!(store.get_state("total_burned").as_deref(), Some("42"));
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RPC TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod rpc_tests {
+    use super::*;
+
+    fn call(state: &AppState, method: &str, params: Value) -> Value {
+        let req = RpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: Some(params),
+            id: Some(json!(1)),
+        };
+        serde_json::to_value(dispatch(state, &req)).unwrap()
+    }
+
+    /// Sign a validator-set change with the wallet stored under `addr`,
+    /// using its current validator_auth_nonce
+    fn sign_validator_change(state: &AppState, addr: &str, action: &str, detail: &str) -> String {
+        let wallets = state.wallets.read();
+        let wallet = &wallets[addr];
+        let message = validator_auth_message(action, addr, detail, wallet.validator_auth_nonce);
+        wallet
+            .keypair
+            .sign_in_domain(crypto::DOMAIN_VALIDATOR, &message)
+    }
+
+    fn deploy_holder_token(state: &AppState) -> String {
+        let mut t = PNP16Token::new(
+            "HolderToken",
+            "HLD",
+            18,
+            1_000,
+            None,
+            AssetClass::Utility,
+            "0xCreator",
+            "DAK_TEST",
+        );
+        t.transfer("0xCreator", "0xaa01", 100).unwrap();
+        t.transfer("0xCreator", "0xaa02", 5).unwrap();
+        t.transfer("0xCreator", "0xbb01", 300).unwrap();
+        state.token_registry.write().deploy_pnp16(t)
+    }
+
+    #[test]
+    fn test_get_holders_with_filters() {
+        let state = AppState::new();
+        let contract = deploy_holder_token(&state);
+        let resp = call(
+            &state,
+            "pnp16_getHolders",
+            json!([contract, [{"memcmp": {"offset": 2, "bytes": "aa"}}]]),
+        );
+        let holders = resp["result"]["holders"].as_array().unwrap();
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0]["address"], "0xaa01");
+        assert_eq!(holders[1]["address"], "0xaa02");
+
+        let resp = call(
+            &state,
+            "pnp16_getHolders",
+            json!([contract, [{"minBalance": "200"}]]),
+        );
+        assert_eq!(resp["result"]["total"], 2);
+    }
+
+    #[test]
+    fn test_get_holders_pagination() {
+        let state = AppState::new();
+        let contract = deploy_holder_token(&state);
+        let resp = call(&state, "pnp16_getHolders", json!([contract, [], 1, 2]));
+        let holders = resp["result"]["holders"].as_array().unwrap();
+        assert_eq!(resp["result"]["total"], 4);
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0]["address"], "0xaa01");
+        assert_eq!(holders[1]["address"], "0xaa02");
+    }
+
+    #[test]
+    fn test_get_holders_rejects_unknown_filter() {
+        let state = AppState::new();
+        let contract = deploy_holder_token(&state);
+        let resp = call(
+            &state,
+            "pnp16_getHolders",
+            json!([contract, [{"dataSize": 8}]]),
+        );
+        assert_eq!(resp["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn test_set_validator_stake_with_signature() {
+        let state = AppState::new();
+        let wallet = Wallet::new();
+        let addr = wallet.keypair.evm_address.clone();
+        state.wallets.write().insert(addr.clone(), wallet);
+
+        let sig = sign_validator_change(&state, &addr, "setValidatorStake", "7000");
+        let resp = call(&state, "pecu_setValidatorStake", json!([addr, "7000", sig]));
+        assert_eq!(resp["result"]["totalStake"], "7000");
+        assert_eq!(state.pot.read().total_stake(), 7_000);
+
+        // The nonce was consumed: the same signature cannot be replayed
+        let replay = call(&state, "pecu_setValidatorStake", json!([addr, "7000", sig]));
+        assert_eq!(replay["error"]["code"], -32001);
+
+        let sig = sign_validator_change(&state, &addr, "setValidatorStake", "9000");
+        call(&state, "pecu_setValidatorStake", json!([addr, "9000", sig]));
+        assert_eq!(state.pot.read().validators.len(), 1);
+        assert_eq!(state.pot.read().total_stake(), 9_000);
+    }
+
+    #[test]
+    fn test_set_validator_stake_requires_wallet_signature() {
+        let state = AppState::new();
+        let wallet = Wallet::new();
+        let addr = wallet.keypair.evm_address.clone();
+        state.wallets.write().insert(addr.clone(), wallet);
+
+        // A GAK key id is not proof of key ownership
+        let gak = call(&state, "gak_connect", json!([addr, "validator-cli", 3600]));
+        let key_id = gak["result"]["keyId"].as_str().unwrap().to_string();
+        let resp = call(
+            &state,
+            "pecu_setValidatorStake",
+            json!([addr, "7000", key_id]),
+        );
+        assert_eq!(resp["error"]["code"], -32001);
+
+        // Signed by another wallet
+        let other = Wallet::new();
+        let message = validator_auth_message("setValidatorStake", &addr, "7000", 0);
+        let forged = other
+            .keypair
+            .sign_in_domain(crypto::DOMAIN_VALIDATOR, &message);
+        let resp = call(
+            &state,
+            "pecu_setValidatorStake",
+            json!([addr, "7000", forged]),
+        );
+        assert_eq!(resp["error"]["code"], -32001);
+
+        // Signed for a different stake
+        let sig = sign_validator_change(&state, &addr, "setValidatorStake", "1");
+        let resp = call(&state, "pecu_setValidatorStake", json!([addr, "7000", sig]));
+        assert_eq!(resp["error"]["code"], -32001);
+        assert_eq!(state.pot.read().total_stake(), 0);
+    }
+
+    #[test]
+    fn test_set_validator_stake_bounded_and_total_saturates() {
+        let state = AppState::new();
+        let wallet = Wallet::new();
+        let addr = wallet.keypair.evm_address.clone();
+        state.wallets.write().insert(addr.clone(), wallet);
+
+        let huge = u128::MAX.to_string();
+        let sig = sign_validator_change(&state, &addr, "setValidatorStake", &huge);
+        let resp = call(&state, "pecu_setValidatorStake", json!([addr, huge, sig]));
+        assert_eq!(resp["error"]["code"], -32602);
+        assert!(state.pot.read().validators.is_empty());
+
+        let mut pot = ProofOfTime::new();
+        pot.set_stake("0xa", u128::MAX);
+        pot.set_stake("0xb", u128::MAX);
+        assert_eq!(pot.total_stake(), u128::MAX);
+    }
+
+    #[test]
+    fn test_get_mempool_limit() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        for nonce in 0..4 {
+            let tx = Transaction::new(
+                TransactionType::Transfer,
+                "alice",
+                "bob",
+                1_000,
+                None,
+                None,
+                false,
+                None,
+                None,
+                nonce,
+            );
+            state.blockchain.add_to_mempool(tx).unwrap();
+        }
+
+        let resp = call(&state, "pecu_getMempool", json!([3]));
+        assert_eq!(resp["result"]["pending"], 4);
+        let txs = resp["result"]["transactions"].as_array().unwrap();
+        assert_eq!(txs.len(), 3);
+        assert_eq!(txs[0]["from"], "alice");
+        assert_eq!(txs[1]["nonce"], 1);
+    }
+
+    #[test]
+    fn test_get_latest_blockhash_and_send_with_it() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+
+        let resp = call(&state, "pecu_getLatestBlockhash", json!([]));
+        let blockhash = resp["result"]["blockhash"].as_str().unwrap().to_string();
+        assert_eq!(blockhash, state.blockchain.latest_block().hash);
+
+        let sent = call(
+            &state,
+            "pecu_sendTransaction",
+            json!(["alice", "bob", "1000", null, blockhash]),
+        );
+        assert_eq!(sent["result"]["status"], "pending");
+
+        let stale = call(
+            &state,
+            "pecu_sendTransaction",
+            json!(["alice", "bob", "1000", null, "not-a-block"]),
+        );
+        assert_eq!(stale["error"]["code"], -32000);
+    }
+
+    #[test]
+    fn test_get_transaction_status_through_mining() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+
+        let sent = call(
+            &state,
+            "pecu_sendTransaction",
+            json!(["alice", "bob", "1000"]),
+        );
+        let hash = sent["result"]["txHash"].as_str().unwrap().to_string();
+
+        let resp = call(&state, "pecu_getTransactionStatus", json!([hash]));
+        assert_eq!(resp["result"]["status"], "pending");
+
+        call(&state, "pecu_mineBlock", json!([]));
+        let resp = call(&state, "pecu_getTransactionStatus", json!([hash]));
+        assert_eq!(resp["result"]["status"], "confirmed");
+        assert_eq!(resp["result"]["blockHeight"], 1);
+
+        let resp = call(&state, "pecu_getTransactionStatus", json!(["0xnope"]));
+        assert!(resp["result"].is_null());
+    }
+
+    #[test]
+    fn test_request_airdrop_disabled_by_default() {
+        let state = AppState::new();
+        let resp = call(&state, "pecu_requestAirdrop", json!(["0xdev", "1000"]));
+        assert_eq!(resp["error"]["code"], -32000);
+        assert!(state.blockchain.mempool.read().is_empty());
+    }
+
+    #[test]
+    fn test_request_airdrop_funds_account_when_enabled() {
+        let mut state = AppState::new();
+        state.faucet_enabled = true;
+
+        let resp = call(&state, "pecu_requestAirdrop", json!(["0xdev", "5000"]));
+        assert_eq!(resp["result"]["status"], "pending");
+        call(&state, "pecu_mineBlock", json!([]));
+        assert_eq!(state.blockchain.get_balance("0xdev"), 5000);
+
+        let too_much = (pecu_novus::rpc::MAX_AIRDROP + 1).to_string();
+        let resp = call(&state, "pecu_requestAirdrop", json!(["0xdev", too_much]));
+        assert_eq!(resp["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn test_get_validator_set_splits_delinquent() {
+        let state = AppState::new();
+        {
+            let mut pot = state.pot.write();
+            pot.register_validator(Validator::new("0xActive", 3_000));
+            let mut stale = Validator::new("0xStale", 2_000);
+            stale.last_seen -= pecu_novus::consensus::DELINQUENT_AFTER_SECONDS + 60;
+            pot.register_validator(stale);
+            let mut offline = Validator::new("0xOffline", 1_000);
+            offline.is_online = false;
+            pot.register_validator(offline);
+        }
+
+        let resp = call(&state, "pecu_getValidatorSet", json!([]));
+        let result = &resp["result"];
+        let addresses = |key: &str| -> Vec<String> {
+            result[key]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v["walletAddress"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(addresses("current"), vec!["0xActive"]);
+        assert_eq!(addresses("delinquent"), vec!["0xStale", "0xOffline"]);
+        assert_eq!(result["activeStake"], "3000");
+        assert_eq!(result["totalStake"], "6000");
+    }
+
+    #[test]
+    fn test_get_leader_schedule_predicts_next_block() {
+        let state = AppState::new();
+        {
+            let mut pot = state.pot.write();
+            for name in ["0xAlice", "0xBob"] {
+                let mut v = Validator::new(name, 1_000);
+                v.uptime_seconds = 86400;
+                pot.register_validator(v);
+            }
+        }
+
+        let resp = call(&state, "pecu_getLeaderSchedule", json!([]));
+        let next_leader = resp["result"]["nextLeader"].as_str().unwrap().to_string();
+        assert_eq!(resp["result"]["nextHeight"], 1);
+        assert_eq!(
+            next_leader,
+            state
+                .pot
+                .read()
+                .leader_for_height(&state.blockchain.latest_block().hash, 1)
+        );
+
+        call(&state, "pecu_mineBlock", json!([]));
+        let resp = call(&state, "pecu_getLeaderSchedule", json!([]));
+        assert_eq!(resp["result"]["currentHeight"], 1);
+        assert_eq!(resp["result"]["currentLeader"], next_leader);
+    }
+
+    #[test]
+    fn test_wait_for_block_returns_once_produced() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+
+        let resp = std::thread::scope(|s| {
+            let waiter = s.spawn(|| call(&state, "pecu_waitForBlock", json!([1, 5000])));
+            std::thread::sleep(std::time::Duration::from_millis(150));
+            call(
+                &state,
+                "pecu_sendTransaction",
+                json!(["alice", "bob", "1000"]),
+            );
+            call(&state, "pecu_mineBlock", json!([]));
+            waiter.join().unwrap()
+        });
+        assert_eq!(resp["result"]["number"], "0x1");
+        assert_eq!(
+            resp["result"]["hash"],
+            state.blockchain.latest_block().hash.as_str()
+        );
+
+        let resp = call(&state, "pecu_waitForBlock", json!([5, 100]));
+        assert_eq!(resp["error"]["code"], -32000);
+    }
+
+    #[test]
+    fn test_get_transaction_receipt_after_commit() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+
+        let sent = call(
+            &state,
+            "pecu_sendTransaction",
+            json!(["alice", "bob", "1000"]),
+        );
+        let hash = sent["result"]["txHash"].as_str().unwrap().to_string();
+        let pending = call(&state, "eth_getTransactionReceipt", json!([hash]));
+        assert!(pending["result"].is_null());
+
+        call(&state, "pecu_mineBlock", json!([]));
+        let receipt = call(&state, "eth_getTransactionReceipt", json!([hash]));
+        let r = &receipt["result"];
+        assert_eq!(r["transactionHash"], hash.as_str());
+        assert_eq!(r["blockNumber"], "0x1");
+        assert_eq!(
+            r["blockHash"],
+            state.blockchain.latest_block().hash.as_str()
+        );
+        assert_eq!(r["status"], "0x1");
+        assert_eq!(r["from"], "alice");
+        assert_eq!(r["to"], "bob");
+        assert_eq!(
+            r["gasUsed"],
+            call(&state, "eth_estimateGas", json!([]))["result"]
+        );
+        assert_eq!(
+            r["effectiveGasPrice"],
+            call(&state, "eth_gasPrice", json!([]))["result"]
+        );
+        let tx = state.blockchain.get_transaction(&hash).unwrap();
+        assert_eq!(r["fee"], format!("0x{:x}", tx.gas_fee));
+    }
+
+    #[test]
+    fn test_get_pot_proof_verifies() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 999_999_999u128);
+        call(
+            &state,
+            "pecu_sendTransaction",
+            json!(["alice", "bob", "1000"]),
+        );
+        call(&state, "pecu_mineBlock", json!([]));
+
+        let resp = call(&state, "pecu_getPotProof", json!([1]));
+        assert_eq!(resp["result"]["height"], 1);
+        let proof: crypto::VdfProof =
+            serde_json::from_value(resp["result"]["proof"].clone()).unwrap();
+        assert!(crypto::verify_vdf(&proof));
+
+        let mut tampered = proof;
+        tampered.output = crypto::sha256(b"forged");
+        assert!(!crypto::verify_vdf(&tampered));
+
+        let missing = call(&state, "pecu_getPotProof", json!([99]));
+        assert!(missing["result"].is_null());
+
+        // No silent fallback to the genesis proof
+        for params in [json!([]), json!(["1"]), json!([-1])] {
+            let bad = call(&state, "pecu_getPotProof", params);
+            assert_eq!(bad["error"]["code"], -32602);
+        }
+    }
+
+    #[test]
+    fn test_get_multiple_accounts_keeps_order() {
+        let state = AppState::new();
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("alice".to_string(), 500u128);
+        state
+            .blockchain
+            .balances
+            .write()
+            .insert("bob".to_string(), 700u128);
+
+        let resp = call(
+            &state,
+            "pecu_getMultipleAccounts",
+            json!([["bob", "nobody", "alice"]]),
+        );
+        let accounts = resp["result"].as_array().unwrap();
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0]["address"], "bob");
+        assert_eq!(accounts[0]["balance_raw"], "700");
+        assert!(accounts[1].is_null());
+        assert_eq!(accounts[2]["balance_raw"], "500");
+
+        let too_many: Vec<String> = (0..=pecu_novus::rpc::MAX_ACCOUNTS_PER_REQUEST)
+            .map(|i| format!("0x{i}"))
+            .collect();
+        let resp = call(&state, "pecu_getMultipleAccounts", json!([too_many]));
+        assert_eq!(resp["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn test_get_wallet_by_either_address() {
+        let state = AppState::new();
+        let created = call(&state, "pecu_createWallet", json!([]));
+        let evm = created["result"]["evmAddress"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let pecu = created["result"]["pecuAddress"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let by_evm = call(&state, "pecu_getWallet", json!([evm]));
+        let by_pecu = call(&state, "pecu_getWallet", json!([pecu]));
+        assert_eq!(by_evm["result"]["walletId"], by_pecu["result"]["walletId"]);
+
+        let bad = call(&state, "pecu_getWallet", json!(["not-base58-0OIl"]));
+        assert_eq!(bad["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn test_deregister_validator_with_signature() {
+        let state = AppState::new();
+        let wallet = Wallet::new();
+        let addr = wallet.keypair.evm_address.clone();
+        state.wallets.write().insert(addr.clone(), wallet);
+        let sig = sign_validator_change(&state, &addr, "setValidatorStake", "7000");
+        call(&state, "pecu_setValidatorStake", json!([addr, "7000", sig]));
+
+        // A GAK key id minted by anyone for this address is not enough
+        let gak = call(&state, "gak_connect", json!([addr, "attacker", 3600]));
+        let key_id = gak["result"]["keyId"].as_str().unwrap().to_string();
+        let denied = call(&state, "pecu_deregisterValidator", json!([addr, key_id]));
+        assert_eq!(denied["error"]["code"], -32001);
+        assert_eq!(state.pot.read().validators.len(), 1);
+
+        let sig = sign_validator_change(&state, &addr, "deregisterValidator", "");
+        let resp = call(&state, "pecu_deregisterValidator", json!([addr, sig]));
+        assert_eq!(resp["result"]["status"], "deregistered");
+        assert_eq!(resp["result"]["totalStake"], "0");
+        assert!(state.pot.read().validators.is_empty());
+        assert!(state.wallets.read()[&addr].validator_node_id.is_none());
+
+        let sig = sign_validator_change(&state, &addr, "deregisterValidator", "");
+        let again = call(&state, "pecu_deregisterValidator", json!([addr, sig]));
+        assert_eq!(again["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn test_sealing_validator_stays_current() {
+        let state = AppState::new();
+        {
+            let mut stale = Validator::new("0xSealer", 1_000);
+            stale.last_seen -= pecu_novus::consensus::DELINQUENT_AFTER_SECONDS + 60;
+            state.pot.write().register_validator(stale);
+        }
+        let resp = call(&state, "pecu_getValidatorSet", json!([]));
+        assert_eq!(resp["result"]["delinquent"][0]["walletAddress"], "0xSealer");
+
+        // The only online validator leads and seals the next block
+        let resp = call(&state, "pecu_mineBlock", json!([]));
+        assert_eq!(resp["result"]["validator"], "0xSealer");
+
+        let resp = call(&state, "pecu_getValidatorSet", json!([]));
+        assert_eq!(resp["result"]["current"][0]["walletAddress"], "0xSealer");
+        assert!(resp["result"]["delinquent"].as_array().unwrap().is_empty());
+        // Time spent delinquent is not credited as uptime
+        assert_eq!(resp["result"]["current"][0]["uptimeSeconds"], 0);
+    }
+
+    mod limits {
+        use super::*;
+        use axum::{
+            body::{Body, HttpBody},
+            http::{Request, StatusCode},
+            routing::post,
+            Router,
+        };
+        use std::time::Duration;
+        use tower::ServiceExt;
+
+        fn slow_router(sleep_ms: u64, limits: RpcLimits) -> Router {
+            let router = Router::new().route(
+                "/slow",
+                post(move || async move {
+                    tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+                    "done"
+                }),
+            );
+            apply_limits(router, &limits)
+        }
+
+        fn slow_request() -> Request<Body> {
+            Request::post("/slow").body(Body::empty()).unwrap()
+        }
+
+        async fn body_json(mut body: impl HttpBody<Data = axum::body::Bytes> + Unpin) -> Value {
+            let mut bytes = Vec::new();
+            while let Some(Ok(chunk)) = body.data().await {
+                bytes.extend_from_slice(&chunk);
+            }
+            serde_json::from_slice(&bytes).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_request_exceeding_timeout_returns_rpc_error() {
+            let router = slow_router(
+                500,
+                RpcLimits {
+                    request_timeout: Duration::from_millis(20),
+                    max_concurrent_requests: 8,
+                },
+            );
+            let resp = router.oneshot(slow_request()).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body = body_json(resp.into_body()).await;
+            assert_eq!(body["error"]["code"], -32000);
+            assert!(body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("timed out"));
+        }
+
+        #[tokio::test]
+        async fn test_requests_beyond_concurrency_limit_rejected() {
+            let router = slow_router(
+                300,
+                RpcLimits {
+                    request_timeout: Duration::from_secs(5),
+                    max_concurrent_requests: 1,
+                },
+            );
+            let in_flight = tokio::spawn(router.clone().oneshot(slow_request()));
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            let rejected = router.clone().oneshot(slow_request()).await.unwrap();
+            assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+            let first = in_flight.await.unwrap().unwrap();
+            assert_eq!(first.status(), StatusCode::OK);
+
+            // Permit released: the next request is served again
+            let again = router.oneshot(slow_request()).await.unwrap();
+            assert_eq!(again.status(), StatusCode::OK);
+        }
+
+        fn rpc_request(method: &str, params: Value) -> Request<Body> {
+            let body = json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+                "id": 1
+            });
+            Request::post("/")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_timed_out_dispatch_keeps_its_permit() {
+            let state = std::sync::Arc::new(AppState::new());
+            let router = rpc_router(
+                state.clone(),
+                &RpcLimits {
+                    request_timeout: Duration::from_millis(50),
+                    max_concurrent_requests: 1,
+                },
+            );
+
+            // Stall every chain read for a while
+            let chain = state.blockchain.chain.clone();
+            let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+            let holder = std::thread::spawn(move || {
+                let _guard = chain.write();
+                locked_tx.send(()).unwrap();
+                std::thread::sleep(Duration::from_millis(400));
+            });
+            locked_rx.recv().unwrap();
+
+            // The client gets a timeout while the dispatch keeps running
+            let timed_out = router
+                .clone()
+                .oneshot(rpc_request("eth_blockNumber", json!([])))
+                .await
+                .unwrap();
+            let body = body_json(timed_out.into_body()).await;
+            assert!(body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("timed out"));
+
+            // ...and still holds the only slot
+            let busy = router
+                .clone()
+                .oneshot(rpc_request("eth_chainId", json!([])))
+                .await
+                .unwrap();
+            assert_eq!(busy.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+            holder.join().unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let served = router
+                .oneshot(rpc_request("eth_chainId", json!([])))
+                .await
+                .unwrap();
+            assert_eq!(served.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn test_pending_block_wait_does_not_hold_request_slot() {
+            let state = std::sync::Arc::new(AppState::new());
+            let router = rpc_router(
+                state.clone(),
+                &RpcLimits {
+                    request_timeout: Duration::from_secs(5),
+                    max_concurrent_requests: 1,
+                },
+            );
+
+            let waiter = tokio::spawn(
+                router
+                    .clone()
+                    .oneshot(rpc_request("pecu_waitForBlock", json!([1, 3000]))),
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            // The only request slot is free while the wait is pending
+            let served = router
+                .oneshot(rpc_request("eth_chainId", json!([])))
+                .await
+                .unwrap();
+            assert_eq!(served.status(), StatusCode::OK);
+
+            let tip = state.blockchain.latest_block();
+            let proof = crypto::compute_vdf(&tip.hash, 10);
+            state
+                .blockchain
+                .produce_block(1, &tip.hash, vec![], "v1", proof)
+                .unwrap();
+
+            let response = waiter.await.unwrap().unwrap();
+            let body = body_json(response.into_body()).await;
+            assert_eq!(body["result"]["number"], "0x1");
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // END-TO-END SCENARIO TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1145,8 +2877,10 @@ mod e2e_tests {
         pot.register_validator(Validator::new(&alice_addr, 1_000_000));
         let txs = bc.drain_mempool(100);
         let seed = "e2e_test_seed";
-        let (proof, validator) = pot.generate_pot_proof(seed);
-        let block = Block::new(1, &bc.latest_block().hash, txs, &validator, proof);
+        let proof = pot.generate_pot_proof(seed);
+        let parent = bc.latest_block().hash;
+        let validator = pot.leader_for_height(&parent, 1);
+        let block = Block::new(1, &parent, txs, &validator, proof);
         bc.commit_block(block).unwrap();
 
         assert_eq!(bc.block_height(), 1);